@@ -5,27 +5,17 @@ use rocket::response::NamedFile;
 use rocket::{get, post, routes, State};
 use rocket_contrib::json::Json;
 use rocket_contrib::serve::StaticFiles;
-use skat::card::Card;
-use skat::game::Round;
+use skat::bock::BockRules;
+use skat::game::{RoundView, Rules};
+use skat::seat::Seat;
+use skat::table::Table;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Mutex;
 
-struct Game {
-    rounds: std::vec::Vec<Round>,
-    player_ids: (u32, u32, u32),
-}
-
-fn rotate_players(game: &mut Game) {
-    game.player_ids = (game.player_ids.1, game.player_ids.2, game.player_ids.0)
-}
-
 #[post("/game")]
-fn create_game(games_map: State<Mutex<HashMap<u32, Game>>>) -> Json<u32> {
-    let game = Game {
-        rounds: std::vec::Vec::new(),
-        player_ids: (0, 0, 0),
-    };
+fn create_game(games_map: State<Mutex<HashMap<u32, Table>>>) -> Json<u32> {
+    let game = Table::new(Rules::default(), BockRules::default());
     let mut rng = rand::thread_rng();
     let mut game_id;
     loop {
@@ -39,7 +29,7 @@ fn create_game(games_map: State<Mutex<HashMap<u32, Game>>>) -> Json<u32> {
 }
 
 #[post("/game/<game_id>/join")]
-fn join_game(games_map: State<Mutex<HashMap<u32, Game>>>, game_id: u32) -> Option<Json<u32>> {
+fn join_game(games_map: State<Mutex<HashMap<u32, Table>>>, game_id: u32) -> Option<Json<u32>> {
     match games_map.lock().unwrap().get_mut(&game_id) {
         None => None,
         Some(game) => {
@@ -52,50 +42,38 @@ fn join_game(games_map: State<Mutex<HashMap<u32, Game>>>, game_id: u32) -> Optio
                 }
             }
 
-            match game.player_ids {
-                (0, 0, 0) => game.player_ids.0 = player_id,
-                (_, 0, 0) => game.player_ids.1 = player_id,
-                (_, _, 0) => game.player_ids.2 = player_id,
-                (_, _, _) => return None, // TODO game full, dealer/spectators?
+            match game.join(player_id) {
+                Ok(_) => Some(Json(player_id)),
+                Err(_) => None, // TODO spectators?
             }
-            Some(Json(player_id))
         }
     }
 }
 
 #[post("/game/<game_id>/round")]
-fn new_round(games_map: State<Mutex<HashMap<u32, Game>>>, game_id: u32) {
+fn new_round(games_map: State<Mutex<HashMap<u32, Table>>>, game_id: u32) -> Option<()> {
     match games_map.lock().unwrap().get_mut(&game_id) {
-        None => panic!(), // TODO
-        Some(game) => {
-            let round = skat::game::new_round();
-            game.rounds.push(round);
-            rotate_players(game);
-        }
+        None => None,
+        // TODO say why, e.g. the current round isn't finished
+        Some(game) => game.next_round().ok().map(|_| ()),
     }
 }
 
-#[get("/game/<game_id>/round?<player_id>")]
-fn get_player_hand(
-    games_map: State<Mutex<HashMap<u32, Game>>>,
+// the dealer sitting out at a table of four picks a seat to look at
+#[get("/game/<game_id>/round?<player_id>&<seat>")]
+fn get_round_view(
+    games_map: State<Mutex<HashMap<u32, Table>>>,
     game_id: u32,
     player_id: u32,
-) -> Option<Json<[Option<Card>; 10]>> {
+    seat: Option<usize>,
+) -> Option<Json<RoundView>> {
     match games_map.lock().unwrap().get(&game_id) {
         None => None,
-        Some(game) => {
-            let (f, m, r) = game.player_ids;
-            let round = &game.rounds[game.rounds.len() - 1];
-            if player_id == f {
-                return Some(Json(round.forehand));
-            } else if player_id == m {
-                return Some(Json(round.middlehand));
-            } else if player_id == r {
-                return Some(Json(round.rearhand));
-            } else {
-                None
-            }
+        Some(game) if game.sits_out(player_id) => {
+            let seat = Seat::from_index(seat.unwrap_or(0))?;
+            game.dealer_view(player_id, seat).map(Json)
         }
+        Some(game) => game.view_for(player_id).map(Json),
     }
 }
 
@@ -110,13 +88,13 @@ fn favicon() -> Option<NamedFile> {
 }
 
 fn main() {
-    let games_map: Mutex<HashMap<u32, Game>> = Mutex::new(HashMap::new());
+    let games_map: Mutex<HashMap<u32, Table>> = Mutex::new(HashMap::new());
     rocket::ignite()
         .manage(games_map)
         .mount("/static", StaticFiles::from("./static"))
         .mount(
             "/api",
-            routes![create_game, join_game, get_player_hand, new_round],
+            routes![create_game, join_game, get_round_view, new_round],
         )
         .mount("/", routes![index, favicon])
         .launch();
@@ -1,10 +1,12 @@
 use crate::config::HOST;
 use reqwest;
 use skat::card::Card;
+use skat::game::RoundView;
+use skat::sort::{sort_hand, SuitOrder};
 use yew::prelude::*;
 
 pub struct PlayerView {
-    hand: [Option<Card>; 10],
+    hand: Vec<Card>,
     game_id: u32,
     player_id: u32,
     name: String,
@@ -18,7 +20,7 @@ pub enum Msg {
     JoinGameRequest(Option<u32>),
     NewRound(Result<bool, reqwest::Error>),
     NewRoundRequest,
-    GetHand(Result<[Option<Card>; 10], reqwest::Error>),
+    GetHand(Result<RoundView, reqwest::Error>),
     GetHandRequest,
 }
 
@@ -33,7 +35,7 @@ impl Component for PlayerView {
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         PlayerView {
-            hand: [None; 10],
+            hand: Vec::new(),
             game_id: 0,
             player_id: 0,
             name: props.name,
@@ -91,8 +93,10 @@ impl Component for PlayerView {
                     PlayerView::get_player_hand_request(game_id, player_id, callback).await;
                 });
             }
-            Msg::GetHand(Ok(hand)) => {
-                self.hand = hand;
+            Msg::GetHand(Ok(view)) => {
+                self.hand = view.hand;
+                let game = view.contract.map(|contract| contract.game);
+                sort_hand(&mut self.hand, game, SuitOrder::Alternating);
                 return true;
             }
             Msg::GetHand(Err(_)) => (), // TODO
@@ -105,7 +109,12 @@ impl Component for PlayerView {
     }
 
     fn view(&self) -> Html {
-        let hand = serde_json::to_string(&self.hand).unwrap();
+        let hand = self
+            .hand
+            .iter()
+            .map(|card| card.symbol())
+            .collect::<Vec<_>>()
+            .join(" ");
         html! {
             <div>
                 <h2> { &self.name } </h2>
@@ -170,7 +179,7 @@ impl PlayerView {
     async fn get_player_hand_request(
         game_id: u32,
         player_id: u32,
-        then: Callback<Result<[Option<Card>; 10], reqwest::Error>>,
+        then: Callback<Result<RoundView, reqwest::Error>>,
     ) {
         let url = format!(
             "{}/api/game/{}/round?player_id={}",
@@ -179,8 +188,8 @@ impl PlayerView {
         match reqwest::Client::new().get(&url).send().await {
             Err(err) => then.emit(Err(err)),
             Ok(res) => {
-                let hand = serde_json::from_str(&res.text().await.unwrap()).unwrap();
-                then.emit(Ok(hand));
+                let view = serde_json::from_str(&res.text().await.unwrap()).unwrap();
+                then.emit(Ok(view));
             }
         }
     }
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contract::GameType;
+use crate::game::{GameResult, Mode, Round};
+use crate::scoring::{ramsch, seeger_fabian, Score};
+
+/// Which games start a series of bock rounds, where every score is doubled,
+/// and how long the series lasts.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub struct BockRules {
+    // deals in a series, usually one for each player
+    pub rounds: u32,
+    // a game lost with 60 points
+    pub sixty_sixty: bool,
+    // a game doubled with kontra
+    pub kontra: bool,
+    // a game worth more than this
+    pub value: Option<u16>,
+    // follow each bock series with a ramsch series of the same length
+    pub ramsch: bool,
+}
+
+impl Default for BockRules {
+    fn default() -> BockRules {
+        BockRules {
+            rounds: 3,
+            sixty_sixty: true,
+            kontra: true,
+            value: Some(100),
+            ramsch: false,
+        }
+    }
+}
+
+/// Tracks the bock and ramsch rounds still to come across a sequence of
+/// deals. Every finished deal is recorded, bock rounds are played first and
+/// then any ramsch rounds.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Series {
+    pub rules: BockRules,
+    bock: u32,
+    ramsch: u32,
+}
+
+impl Series {
+    pub fn new(rules: BockRules) -> Series {
+        Series {
+            rules,
+            bock: 0,
+            ramsch: 0,
+        }
+    }
+
+    /// Whether the next deal is a bock round.
+    pub fn is_bock(&self) -> bool {
+        self.bock > 0
+    }
+
+    /// Whether the next deal has to be played as a ramsch.
+    pub fn is_ramsch(&self) -> bool {
+        self.bock == 0 && self.ramsch > 0
+    }
+
+    /// Record a finished deal and return its score, doubled if it was a bock
+    /// round. Passed in deals still count towards the series but have no
+    /// score, as do unfinished ones which aren't recorded at all.
+    pub fn record(&mut self, round: &Round, players: usize) -> Option<Score> {
+        if round.mode() != Mode::Finished {
+            return None;
+        }
+
+        let bock = self.is_bock();
+        if bock {
+            self.bock -= 1;
+        } else if self.ramsch > 0 {
+            self.ramsch -= 1;
+        }
+
+        if let Some(mut result) = round.result() {
+            let triggered = self.triggers(&result);
+            if bock {
+                result.modifier *= 2;
+            }
+            let score = seeger_fabian(&result, players);
+            if triggered {
+                self.bock += self.rules.rounds;
+                if self.rules.ramsch {
+                    self.ramsch += self.rules.rounds;
+                }
+            }
+            return Some(score);
+        }
+
+        round.ramsch_result().map(|result| {
+            let mut score = ramsch(&result);
+            if bock {
+                score.value *= 2;
+                score.points.iter_mut().for_each(|points| *points *= 2);
+            }
+            score
+        })
+    }
+
+    // map_or rather than is_some_and which needs a newer Rust than the frontend
+    #[allow(clippy::unnecessary_map_or)]
+    fn triggers(&self, result: &GameResult) -> bool {
+        (self.rules.sixty_sixty && result.contract.game != GameType::Null && result.points == 60)
+            || (self.rules.kontra && result.modifier > 1)
+            || self.rules.value.map_or(false, |value| result.value > value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::Contract;
+    use crate::game::{new_round, play_out};
+    use crate::seat::Seat;
+
+    fn ramsch_round() -> Round {
+        let mut round = new_round();
+        round.play_ramsch();
+        play_out(&mut round);
+        round
+    }
+
+    #[test]
+    fn test_unfinished_round_not_recorded() {
+        let mut series = Series::new(BockRules::default());
+        series.bock = 1;
+        assert_eq!(series.record(&new_round(), 3), None);
+        assert!(series.is_bock());
+    }
+
+    #[test]
+    fn test_bock_doubles_score() {
+        let round = ramsch_round();
+        let normal = Series::new(BockRules::default()).record(&round, 3).unwrap();
+
+        let mut series = Series::new(BockRules {
+            ramsch: true,
+            ..BockRules::default()
+        });
+        series.bock = 1;
+        series.ramsch = 1;
+        assert!(series.is_bock() && !series.is_ramsch());
+        let bock = series.record(&round, 3).unwrap();
+        assert_eq!(bock.value, normal.value * 2);
+        assert!(!series.is_bock() && series.is_ramsch());
+        assert_eq!(series.record(&round, 3), Some(normal));
+        assert!(!series.is_bock() && !series.is_ramsch());
+    }
+
+    #[test]
+    fn test_triggers() {
+        let series = Series::new(BockRules::default());
+        let result = GameResult {
+            declarer: Seat::Forehand,
+            contract: Contract::new(GameType::Grand),
+            bid: 18,
+            won: false,
+            points: 60,
+            value: 48,
+            overbid: false,
+            schneider: false,
+            schwarz: false,
+            modifier: 1,
+        };
+        assert!(series.triggers(&result));
+        assert!(!series.triggers(&GameResult {
+            points: 59,
+            ..result
+        }));
+        assert!(series.triggers(&GameResult {
+            points: 59,
+            modifier: 2,
+            ..result
+        }));
+        assert!(series.triggers(&GameResult {
+            points: 59,
+            value: 120,
+            ..result
+        }));
+    }
+}
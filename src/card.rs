@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ParseCardError;
 
 #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
 pub enum Suit {
@@ -25,3 +29,295 @@ pub struct Card {
     pub suit: Suit,
     pub rank: Rank,
 }
+
+impl Rank {
+    /// The card points (Augen) a card of this rank is worth.
+    pub fn points(&self) -> u8 {
+        match self {
+            Rank::Ace => 11,
+            Rank::Ten => 10,
+            Rank::King => 4,
+            Rank::Queen => 3,
+            Rank::Jack => 2,
+            Rank::Nine | Rank::Eight | Rank::Seven => 0,
+        }
+    }
+}
+
+impl Card {
+    pub fn points(&self) -> u8 {
+        self.rank.points()
+    }
+
+    /// The card's position in the deck from the club seven to the diamond
+    /// jack, each card's bit in a `CardSet`.
+    pub fn index(&self) -> u8 {
+        self.suit as u8 * 8 + self.rank as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Card> {
+        Some(Card {
+            suit: *SUITS.get(index as usize / 8)?,
+            rank: RANKS[index as usize % 8],
+        })
+    }
+}
+
+impl Suit {
+    /// The suit's letter in the two letter notation used by the ISS.
+    pub fn letter(&self) -> char {
+        match self {
+            Suit::Club => 'C',
+            Suit::Spade => 'S',
+            Suit::Heart => 'H',
+            Suit::Diamond => 'D',
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Suit::Club => '♣',
+            Suit::Spade => '♠',
+            Suit::Heart => '♥',
+            Suit::Diamond => '♦',
+        }
+    }
+
+    /// The name on a French deck as used in Germany.
+    pub fn german(&self) -> &'static str {
+        match self {
+            Suit::Club => "Kreuz",
+            Suit::Spade => "Pik",
+            Suit::Heart => "Herz",
+            Suit::Diamond => "Karo",
+        }
+    }
+}
+
+impl Rank {
+    /// The rank's letter in the ISS notation, the ten is T.
+    pub fn letter(&self) -> char {
+        match self {
+            Rank::Seven => '7',
+            Rank::Eight => '8',
+            Rank::Nine => '9',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ten => 'T',
+            Rank::Ace => 'A',
+            Rank::Jack => 'J',
+        }
+    }
+
+    pub fn german(&self) -> &'static str {
+        match self {
+            Rank::Seven => "Sieben",
+            Rank::Eight => "Acht",
+            Rank::Nine => "Neun",
+            Rank::Queen => "Dame",
+            Rank::King => "König",
+            Rank::Ten => "Zehn",
+            Rank::Ace => "Ass",
+            Rank::Jack => "Bube",
+        }
+    }
+}
+
+impl Card {
+    /// The card with a suit symbol, like ♣J.
+    pub fn symbol(&self) -> String {
+        format!("{}{}", self.suit.symbol(), self.rank.letter())
+    }
+
+    /// The card's German name, like Kreuz Bube.
+    pub fn german(&self) -> String {
+        format!("{} {}", self.suit.german(), self.rank.german())
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// Cards display in the ISS notation, like CJ, SA, HT or D7.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.suit, self.rank)
+    }
+}
+
+/// The suits from highest to lowest.
+pub const SUITS: [Suit; 4] = [Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond];
+
+/// The ranks in the order of a trump suit, lowest first.
+pub const RANKS: [Rank; 8] = [
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ten,
+    Rank::Ace,
+    Rank::Jack,
+];
+
+/// Reads a letter, a symbol or a German name, ignoring case.
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseCardError::Empty);
+        }
+        let outline = ['♧', '♤', '♡', '♢'];
+        SUITS
+            .iter()
+            .zip(outline.iter())
+            .find(|(suit, outline)| {
+                s.eq_ignore_ascii_case(&suit.letter().to_string())
+                    || s == suit.symbol().to_string()
+                    || s == outline.to_string()
+                    || s.to_lowercase() == suit.german().to_lowercase()
+            })
+            .map(|(suit, _)| *suit)
+            .ok_or(ParseCardError::UnknownSuit)
+    }
+}
+
+/// Reads a letter, 10 for the ten or a German name, ignoring case.
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Rank, ParseCardError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseCardError::Empty);
+        }
+        if s == "10" {
+            return Ok(Rank::Ten);
+        }
+        RANKS
+            .iter()
+            .find(|rank| {
+                s.eq_ignore_ascii_case(&rank.letter().to_string())
+                    || s.to_lowercase() == rank.german().to_lowercase()
+            })
+            .copied()
+            .ok_or(ParseCardError::UnknownRank)
+    }
+}
+
+/// Reads the ISS notation, a suit symbol followed by the rank or the German
+/// name with the suit and rank separated by a space.
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        let s = s.trim();
+        let (suit, rank) = match s.find(char::is_whitespace) {
+            Some(i) => s.split_at(i),
+            None => match s.chars().next() {
+                Some(c) => s.split_at(c.len_utf8()),
+                None => return Err(ParseCardError::Empty),
+            },
+        };
+        Ok(Card {
+            suit: suit.parse()?,
+            rank: rank.parse()?,
+        })
+    }
+}
+
+/// Parse a whole hand like "CJ SJ HA HT H9", the cards can be separated by
+/// spaces or commas.
+pub fn parse_hand(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut words = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty());
+    let mut cards = Vec::new();
+    while let Some(word) = words.next() {
+        let card = match word.parse::<Card>() {
+            Ok(card) => card,
+            // German names take two words
+            Err(_) => Card {
+                suit: word.parse()?,
+                rank: words.next().ok_or(ParseCardError::UnknownRank)?.parse()?,
+            },
+        };
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/// Write cards in the ISS notation separated by spaces, the inverse of
+/// `parse_hand`.
+pub fn format_hand(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let card = Card {
+            suit: Suit::Heart,
+            rank: Rank::Ten,
+        };
+        assert_eq!(card.to_string(), "HT");
+        assert_eq!(card.symbol(), "♥T");
+        assert_eq!(card.german(), "Herz Zehn");
+    }
+
+    #[test]
+    fn test_parse() {
+        let jack = Card {
+            suit: Suit::Club,
+            rank: Rank::Jack,
+        };
+        for s in ["CJ", "cj", "♣J", "♧J", " CJ ", "Kreuz Bube", "kreuz bube"].iter() {
+            assert_eq!(s.parse::<Card>(), Ok(jack));
+        }
+        assert_eq!("D10".parse::<Rank>(), Err(ParseCardError::UnknownRank));
+        assert_eq!("D10".parse::<Card>().map(|card| card.rank), Ok(Rank::Ten));
+        assert_eq!("XJ".parse::<Card>(), Err(ParseCardError::UnknownSuit));
+        assert_eq!("C1".parse::<Card>(), Err(ParseCardError::UnknownRank));
+        assert_eq!("".parse::<Card>(), Err(ParseCardError::Empty));
+
+        for suit in SUITS.iter() {
+            for rank in RANKS.iter() {
+                let card = Card {
+                    suit: *suit,
+                    rank: *rank,
+                };
+                assert_eq!(card.to_string().parse(), Ok(card));
+                assert_eq!(card.symbol().parse(), Ok(card));
+                assert_eq!(card.german().parse(), Ok(card));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_hand() {
+        let hand = parse_hand("CJ SJ, HA  Herz Zehn ♥9").unwrap();
+        assert_eq!(format_hand(&hand), "CJ SJ HA HT H9");
+        assert_eq!(parse_hand(&format_hand(&hand)), Ok(hand));
+        assert_eq!(parse_hand(""), Ok(vec![]));
+        assert_eq!(parse_hand("CJ Herz"), Err(ParseCardError::UnknownRank));
+        assert_eq!(parse_hand("CJ X7"), Err(ParseCardError::UnknownSuit));
+    }
+}
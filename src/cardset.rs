@@ -0,0 +1,289 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign};
+
+use crate::card::{Card, Rank, Suit};
+use crate::contract::GameType;
+use crate::sort::{sort_hand, SuitOrder};
+
+const ALL: u32 = u32::MAX;
+
+/// A set of cards with one bit per card, see `Card::index`. Hands, the skat
+/// and the cards a side has won are all card sets. It serializes as a list
+/// of cards.
+#[derive(Serialize, Deserialize, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(from = "Vec<Card>", into = "Vec<Card>")]
+pub struct CardSet(u32);
+
+impl CardSet {
+    pub fn new() -> CardSet {
+        CardSet(0)
+    }
+
+    /// The whole deck.
+    pub fn all() -> CardSet {
+        CardSet(ALL)
+    }
+
+    pub fn from_bits(bits: u32) -> CardSet {
+        CardSet(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Every card of a suit, jacks included.
+    pub fn suit(suit: Suit) -> CardSet {
+        CardSet(0xff << (suit as u32 * 8))
+    }
+
+    /// The four cards of a rank.
+    pub fn rank(rank: Rank) -> CardSet {
+        CardSet(0x0101_0101 << rank as u32)
+    }
+
+    /// Add a card, returns false if it was already in the set.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let had = self.contains(card);
+        self.0 |= bit(card);
+        !had
+    }
+
+    /// Take out a card, returns false if it wasn't in the set.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let had = self.contains(card);
+        self.0 &= !bit(card);
+        had
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & bit(card) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_subset(&self, other: CardSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The cards by suit from clubs to diamonds and by rank from the seven
+    /// to the jack within a suit.
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+
+    /// The cards the way a player would sort them for a game, see
+    /// `sort_hand`.
+    pub fn sorted(&self, game: Option<GameType>, order: SuitOrder) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.iter().collect();
+        sort_hand(&mut cards, game, order);
+        cards
+    }
+
+    /// The card points the set is worth.
+    pub fn points(&self) -> u8 {
+        self.iter().map(|card| card.points()).sum()
+    }
+}
+
+fn bit(card: Card) -> u32 {
+    1 << card.index()
+}
+
+/// Iterates a card set from the lowest bit.
+pub struct Iter(u32);
+
+impl Iterator for Iter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Card::from_index(index as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> CardSet {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(iter: I) -> CardSet {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl From<Vec<Card>> for CardSet {
+    fn from(cards: Vec<Card>) -> CardSet {
+        cards.into_iter().collect()
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(set: CardSet) -> Vec<Card> {
+        set.iter().collect()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: CardSet) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: CardSet) {
+        self.0 &= other.0;
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, other: CardSet) {
+        self.0 &= !other.0;
+    }
+}
+
+/// The rest of the deck.
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        CardSet(!self.0)
+    }
+}
+
+impl fmt::Debug for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|card| card.to_string()))
+            .finish()
+    }
+}
+
+/// The cards in the ISS notation separated by spaces.
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cards: Vec<String> = self.iter().map(|card| card.to_string()).collect();
+        write!(f, "{}", cards.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_hand;
+
+    fn set(cards: &str) -> CardSet {
+        parse_hand(cards).unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut hand = set("HA CJ D7 S8");
+        assert_eq!(hand.len(), 4);
+        assert!(hand.contains("CJ".parse().unwrap()));
+        assert!(!hand.insert("CJ".parse().unwrap()));
+        assert!(hand.remove("CJ".parse().unwrap()));
+        assert!(!hand.remove("CJ".parse().unwrap()));
+        assert_eq!(hand.to_string(), "S8 HA D7");
+
+        let other = set("HA HT");
+        assert_eq!(hand | other, set("S8 HA HT D7"));
+        assert_eq!(hand & other, set("HA"));
+        assert_eq!(hand - other, set("S8 D7"));
+        assert_eq!((!hand).len(), 29);
+        assert!(set("HA").is_subset(hand));
+        assert_eq!(hand.points(), 11);
+        assert_eq!(CardSet::all().len(), 32);
+        assert_eq!(CardSet::all().points(), 120);
+    }
+
+    #[test]
+    fn test_iter() {
+        let hand = set("DJ HT C7 SQ");
+        let cards: Vec<Card> = hand.iter().collect();
+        assert_eq!(cards, parse_hand("C7 SQ HT DJ").unwrap());
+        assert_eq!(hand.iter().size_hint(), (4, Some(4)));
+        assert_eq!(
+            hand.sorted(Some(GameType::Grand), SuitOrder::Standard),
+            parse_hand("DJ C7 SQ HT").unwrap()
+        );
+        assert_eq!(CardSet::all().iter().count(), 32);
+    }
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(CardSet::suit(Suit::Heart).len(), 8);
+        assert_eq!(CardSet::rank(Rank::Jack), set("CJ SJ HJ DJ"));
+    }
+
+    #[test]
+    fn test_list_conversion() {
+        // serde goes through the list of cards
+        let hand = set("HT CJ");
+        let cards: Vec<Card> = hand.into();
+        assert_eq!(cards, parse_hand("CJ HT").unwrap());
+        assert_eq!(CardSet::from(cards), hand);
+    }
+}
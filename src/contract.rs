@@ -0,0 +1,594 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::card::{Card, Rank, Suit};
+use crate::cardset::CardSet;
+use crate::seat::Seat;
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum GameType {
+    Suit(Suit),
+    Grand,
+    Null,
+}
+
+impl GameType {
+    pub fn all() -> [GameType; 6] {
+        [
+            GameType::Suit(Suit::Club),
+            GameType::Suit(Suit::Spade),
+            GameType::Suit(Suit::Heart),
+            GameType::Suit(Suit::Diamond),
+            GameType::Grand,
+            GameType::Null,
+        ]
+    }
+
+    /// What the game is worth per level, for null the value of a plain null.
+    pub fn base_value(&self) -> u16 {
+        match self {
+            GameType::Suit(Suit::Club) => 12,
+            GameType::Suit(Suit::Spade) => 11,
+            GameType::Suit(Suit::Heart) => 10,
+            GameType::Suit(Suit::Diamond) => 9,
+            GameType::Grand => 24,
+            GameType::Null => 23,
+        }
+    }
+
+    /// Every trump from the highest down, these are the cards that count
+    /// as matadors.
+    pub fn trumps(&self) -> Vec<Card> {
+        let mut trumps = Vec::new();
+        if *self == GameType::Null {
+            return trumps;
+        }
+        for suit in [Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond].iter() {
+            trumps.push(Card {
+                suit: *suit,
+                rank: Rank::Jack,
+            });
+        }
+        if let GameType::Suit(suit) = self {
+            let ranks = [
+                Rank::Ace,
+                Rank::Ten,
+                Rank::King,
+                Rank::Queen,
+                Rank::Nine,
+                Rank::Eight,
+                Rank::Seven,
+            ];
+            for rank in ranks.iter() {
+                trumps.push(Card {
+                    suit: *suit,
+                    rank: *rank,
+                });
+            }
+        }
+        trumps
+    }
+
+    /// Whether the cards are "with" or "without" the top trumps and how many
+    /// in an unbroken run from the club jack.
+    pub fn matadors(&self, cards: &[Card]) -> (bool, u8) {
+        let trumps = self.trumps();
+        let with = match trumps.first() {
+            Some(top) => cards.contains(top),
+            None => return (false, 0),
+        };
+        let count = trumps
+            .iter()
+            .take_while(|trump| cards.contains(trump) == with)
+            .count();
+        (with, count as u8)
+    }
+
+    /// Every trump in the game, empty for null.
+    pub fn trump_set(&self) -> CardSet {
+        match self {
+            GameType::Suit(suit) => CardSet::rank(Rank::Jack) | CardSet::suit(*suit),
+            GameType::Grand => CardSet::rank(Rank::Jack),
+            GameType::Null => CardSet::new(),
+        }
+    }
+
+    /// The cards that follow a suit in the game, i.e. the suit without any
+    /// trumps.
+    pub fn suit_set(&self, suit: Suit) -> CardSet {
+        CardSet::suit(suit) - self.trump_set()
+    }
+
+    /// The cards that follow a lead, trumps if it's a trump.
+    pub fn follow_set(&self, lead: Card) -> CardSet {
+        match self.suit_of(&lead) {
+            Some(suit) => self.suit_set(suit),
+            None => self.trump_set(),
+        }
+    }
+
+    pub fn is_trump(&self, card: &Card) -> bool {
+        match self {
+            GameType::Suit(suit) => card.rank == Rank::Jack || card.suit == *suit,
+            GameType::Grand => card.rank == Rank::Jack,
+            GameType::Null => false,
+        }
+    }
+
+    /// The suit a card belongs to when following suit, None for trumps. Jacks
+    /// are trumps in suit games and grand but ordinary cards in null.
+    pub fn suit_of(&self, card: &Card) -> Option<Suit> {
+        if self.is_trump(card) {
+            None
+        } else {
+            Some(card.suit)
+        }
+    }
+
+    /// The player whose card wins a complete trick, cards are indexed by
+    /// seat.
+    pub fn trick_winner(&self, trick: &[Card; 3], lead: Seat) -> Seat {
+        let lead_card = &trick[lead.index()];
+        let mut winner = lead;
+        for player in Seat::all().iter().copied() {
+            if self.strength(&trick[player.index()], lead_card)
+                > self.strength(&trick[winner.index()], lead_card)
+            {
+                winner = player;
+            }
+        }
+        winner
+    }
+
+    // How strong a card is in a trick, cards that neither follow the lead
+    // nor are trumps can't win and have no strength.
+    fn strength(&self, card: &Card, lead: &Card) -> u8 {
+        if *self == GameType::Null {
+            return if card.suit == lead.suit {
+                1 + null_order(card.rank)
+            } else {
+                0
+            };
+        }
+
+        if card.rank == Rank::Jack {
+            // club > spade > heart > diamond
+            40 - card.suit as u8
+        } else if self.is_trump(card) {
+            20 + card.rank as u8
+        } else if self.suit_of(card) == self.suit_of(lead) {
+            1 + card.rank as u8
+        } else {
+            0
+        }
+    }
+}
+
+// In null the ten sits in its natural place between the nine and the jack.
+pub(crate) fn null_order(rank: Rank) -> u8 {
+    match rank {
+        Rank::Seven => 0,
+        Rank::Eight => 1,
+        Rank::Nine => 2,
+        Rank::Ten => 3,
+        Rank::Jack => 4,
+        Rank::Queen => 5,
+        Rank::King => 6,
+        Rank::Ace => 7,
+    }
+}
+
+/// Suit games are named by their trump suit, like Clubs.
+impl fmt::Display for GameType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameType::Suit(Suit::Club) => write!(f, "Clubs"),
+            GameType::Suit(Suit::Spade) => write!(f, "Spades"),
+            GameType::Suit(Suit::Heart) => write!(f, "Hearts"),
+            GameType::Suit(Suit::Diamond) => write!(f, "Diamonds"),
+            GameType::Grand => write!(f, "Grand"),
+            GameType::Null => write!(f, "Null"),
+        }
+    }
+}
+
+/// The game announced by the declarer along with any extra levels they've
+/// committed to.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub struct Contract {
+    pub game: GameType,
+    pub hand: bool,
+    pub schneider: bool,
+    pub schwarz: bool,
+    pub ouvert: bool,
+}
+
+impl Contract {
+    // a contract with no announcements, played after picking up the skat
+    pub fn new(game: GameType) -> Contract {
+        Contract {
+            game,
+            hand: false,
+            schneider: false,
+            schwarz: false,
+            ouvert: false,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self.game {
+            // null can be played open with or without the skat but has no
+            // schneider or schwarz
+            GameType::Null => !self.schneider && !self.schwarz,
+            _ => {
+                if !self.hand && (self.schneider || self.schwarz || self.ouvert) {
+                    // announcements are only allowed when playing hand
+                    return false;
+                }
+                // each announcement includes the ones below it
+                (self.schneider || !self.schwarz) && (self.schwarz || !self.ouvert)
+            }
+        }
+    }
+
+    /// Every valid contract for a declarer that has or hasn't picked up the
+    /// skat.
+    pub fn all(hand: bool) -> Vec<Contract> {
+        let mut contracts = Vec::new();
+        for game in GameType::all().iter() {
+            for level in 0..4 {
+                let contract = Contract {
+                    hand,
+                    schneider: level >= 1 && *game != GameType::Null,
+                    schwarz: level >= 2 && *game != GameType::Null,
+                    ouvert: level == 3 || (level == 1 && *game == GameType::Null),
+                    ..Contract::new(*game)
+                };
+                if contract.is_valid() && !contracts.contains(&contract) {
+                    contracts.push(contract);
+                }
+            }
+        }
+        contracts
+    }
+
+    /// The value of the game given all 12 of the declarer's cards (their hand
+    /// and the skat) and whether schneider or schwarz was reached by either
+    /// side. Announced levels always count.
+    pub fn value(&self, cards: &[Card], schneider: bool, schwarz: bool) -> u16 {
+        if self.game == GameType::Null {
+            return match (self.hand, self.ouvert) {
+                (false, false) => 23,
+                (true, false) => 35,
+                (false, true) => 46,
+                (true, true) => 59,
+            };
+        }
+
+        let (_, matadors) = self.game.matadors(cards);
+        let levels = [
+            true, // game
+            self.hand,
+            schneider || self.schneider,
+            self.schneider,
+            schwarz || self.schwarz,
+            self.schwarz,
+            self.ouvert,
+        ];
+        let multiplier = matadors as u16 + levels.iter().filter(|level| **level).count() as u16;
+        multiplier * self.game.base_value()
+    }
+}
+
+/// The game followed by its announcements, like Clubs Hand Schneider
+/// announced. Only the highest announcement is named as each includes the
+/// ones below it.
+impl fmt::Display for Contract {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.game)?;
+        if self.hand {
+            write!(f, " Hand")?;
+        }
+        if self.ouvert {
+            write!(f, " Ouvert")
+        } else if self.schwarz {
+            write!(f, " Schwarz announced")
+        } else if self.schneider {
+            write!(f, " Schneider announced")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let clubs = Contract {
+            hand: true,
+            schneider: true,
+            ..Contract::new(GameType::Suit(Suit::Club))
+        };
+        assert_eq!(clubs.to_string(), "Clubs Hand Schneider announced");
+        let null = Contract {
+            ouvert: true,
+            ..Contract::new(GameType::Null)
+        };
+        assert_eq!(null.to_string(), "Null Ouvert");
+        assert_eq!(Contract::new(GameType::Grand).to_string(), "Grand");
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let grand = Contract::new(GameType::Grand);
+        assert!(grand.is_valid());
+        assert!(!Contract {
+            schneider: true,
+            ..grand
+        }
+        .is_valid());
+        assert!(Contract {
+            hand: true,
+            schneider: true,
+            ..grand
+        }
+        .is_valid());
+        assert!(!Contract {
+            hand: true,
+            schwarz: true,
+            ..grand
+        }
+        .is_valid());
+        assert!(!Contract {
+            hand: true,
+            schneider: true,
+            ouvert: true,
+            ..grand
+        }
+        .is_valid());
+        assert!(Contract {
+            hand: true,
+            schneider: true,
+            schwarz: true,
+            ouvert: true,
+            ..grand
+        }
+        .is_valid());
+
+        let null = Contract::new(GameType::Null);
+        assert!(Contract {
+            ouvert: true,
+            ..null
+        }
+        .is_valid());
+        assert!(!Contract {
+            hand: true,
+            schneider: true,
+            ..null
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_suit_of() {
+        let jack = Card {
+            suit: Suit::Club,
+            rank: Rank::Jack,
+        };
+        let heart = Card {
+            suit: Suit::Heart,
+            rank: Rank::Seven,
+        };
+        let hearts = GameType::Suit(Suit::Heart);
+        assert_eq!(hearts.suit_of(&jack), None);
+        assert_eq!(hearts.suit_of(&heart), None);
+        assert_eq!(GameType::Grand.suit_of(&jack), None);
+        assert_eq!(GameType::Grand.suit_of(&heart), Some(Suit::Heart));
+        assert_eq!(GameType::Null.suit_of(&jack), Some(Suit::Club));
+    }
+
+    fn trick(cards: [(Suit, Rank); 3]) -> [Card; 3] {
+        let mut trick = [Card {
+            suit: Suit::Club,
+            rank: Rank::Seven,
+        }; 3];
+        for (i, (suit, rank)) in cards.iter().enumerate() {
+            trick[i] = Card {
+                suit: *suit,
+                rank: *rank,
+            };
+        }
+        trick
+    }
+
+    #[test]
+    fn test_trick_winner() {
+        let hearts = GameType::Suit(Suit::Heart);
+        let jacks = trick([
+            (Suit::Diamond, Rank::Jack),
+            (Suit::Club, Rank::Jack),
+            (Suit::Spade, Rank::Jack),
+        ]);
+        assert_eq!(
+            hearts.trick_winner(&jacks, Seat::Forehand),
+            Seat::Middlehand
+        );
+        assert_eq!(
+            GameType::Grand.trick_winner(&jacks, Seat::Rearhand),
+            Seat::Middlehand
+        );
+
+        let tricks = trick([
+            (Suit::Spade, Rank::Ten),
+            (Suit::Spade, Rank::Ace),
+            (Suit::Heart, Rank::Seven),
+        ]);
+        // the smallest trump beats the ace
+        assert_eq!(hearts.trick_winner(&tricks, Seat::Forehand), Seat::Rearhand);
+        // unless there are no trumps
+        assert_eq!(
+            GameType::Grand.trick_winner(&tricks, Seat::Forehand),
+            Seat::Middlehand
+        );
+        // a card that doesn't follow can't win even when led after
+        assert_eq!(
+            GameType::Grand.trick_winner(&tricks, Seat::Rearhand),
+            Seat::Rearhand
+        );
+
+        let suit_trumps = trick([
+            (Suit::Heart, Rank::Ace),
+            (Suit::Diamond, Rank::Jack),
+            (Suit::Heart, Rank::Ten),
+        ]);
+        assert_eq!(
+            hearts.trick_winner(&suit_trumps, Seat::Forehand),
+            Seat::Middlehand
+        );
+        assert_eq!(
+            GameType::Null.trick_winner(&suit_trumps, Seat::Forehand),
+            Seat::Forehand
+        );
+
+        let null = trick([
+            (Suit::Club, Rank::Nine),
+            (Suit::Club, Rank::Ten),
+            (Suit::Club, Rank::Jack),
+        ]);
+        assert_eq!(
+            GameType::Null.trick_winner(&null, Seat::Forehand),
+            Seat::Rearhand
+        );
+        assert_eq!(
+            GameType::Grand.trick_winner(&null, Seat::Forehand),
+            Seat::Rearhand
+        );
+        let null = trick([
+            (Suit::Club, Rank::Nine),
+            (Suit::Club, Rank::Ten),
+            (Suit::Club, Rank::Queen),
+        ]);
+        assert_eq!(
+            GameType::Null.trick_winner(&null, Seat::Forehand),
+            Seat::Rearhand
+        );
+        assert_eq!(
+            GameType::Grand.trick_winner(&null, Seat::Forehand),
+            Seat::Middlehand
+        );
+    }
+
+    fn cards(cards: &[(Suit, Rank)]) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|(suit, rank)| Card {
+                suit: *suit,
+                rank: *rank,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matadors() {
+        let hearts = GameType::Suit(Suit::Heart);
+        let hand = cards(&[
+            (Suit::Club, Rank::Jack),
+            (Suit::Spade, Rank::Jack),
+            (Suit::Diamond, Rank::Jack),
+            (Suit::Heart, Rank::Ace),
+        ]);
+        assert_eq!(hearts.matadors(&hand), (true, 2));
+        assert_eq!(GameType::Grand.matadors(&hand), (true, 2));
+        assert_eq!(GameType::Null.matadors(&hand), (false, 0));
+
+        let hand = cards(&[(Suit::Heart, Rank::Ten), (Suit::Heart, Rank::King)]);
+        assert_eq!(hearts.matadors(&hand), (false, 5));
+        assert_eq!(GameType::Grand.matadors(&hand), (false, 4));
+
+        let hand = cards(&[
+            (Suit::Club, Rank::Jack),
+            (Suit::Spade, Rank::Jack),
+            (Suit::Heart, Rank::Jack),
+            (Suit::Diamond, Rank::Jack),
+            (Suit::Heart, Rank::Ace),
+            (Suit::Heart, Rank::Ten),
+            (Suit::Heart, Rank::Queen),
+        ]);
+        assert_eq!(hearts.matadors(&hand), (true, 6));
+        assert_eq!(GameType::Grand.matadors(&hand), (true, 4));
+    }
+
+    #[test]
+    fn test_value() {
+        let hand = cards(&[(Suit::Club, Rank::Jack), (Suit::Heart, Rank::Jack)]);
+        let clubs = Contract::new(GameType::Suit(Suit::Club));
+        // with 1, game 2
+        assert_eq!(clubs.value(&hand, false, false), 24);
+        // with 1, game 2, schneider 3, schwarz 4
+        assert_eq!(clubs.value(&hand, true, true), 48);
+
+        let grand = Contract {
+            hand: true,
+            schneider: true,
+            ..Contract::new(GameType::Grand)
+        };
+        // with 1, game 2, hand 3, schneider 4, announced 5
+        assert_eq!(grand.value(&hand, true, false), 120);
+
+        let diamonds = Contract {
+            hand: true,
+            schneider: true,
+            schwarz: true,
+            ouvert: true,
+            ..Contract::new(GameType::Suit(Suit::Diamond))
+        };
+        // without 2, game 3, hand 4, schneider 5, announced 6, schwarz 7,
+        // announced 8, ouvert 9
+        let hand = cards(&[(Suit::Heart, Rank::Jack)]);
+        assert_eq!(diamonds.value(&hand, false, false), 9 * 9);
+        // without all 11 trumps
+        assert_eq!(diamonds.value(&[], false, false), 9 * 18);
+        let hand = cards(&[(Suit::Spade, Rank::Jack)]);
+        assert_eq!(diamonds.value(&hand, false, false), 9 * 8);
+
+        let null = Contract::new(GameType::Null);
+        assert_eq!(null.value(&hand, false, false), 23);
+        let null = Contract {
+            hand: true,
+            ouvert: true,
+            ..null
+        };
+        assert_eq!(null.value(&hand, false, false), 59);
+    }
+
+    #[test]
+    fn test_all() {
+        // suit and grand with each of the 4 levels, null and null ouvert
+        assert_eq!(Contract::all(true).len(), 5 * 4 + 2);
+        // suit, grand, null and null ouvert
+        assert_eq!(Contract::all(false).len(), 5 + 2);
+        assert!(Contract::all(true).iter().all(|c| c.is_valid() && c.hand));
+        assert!(Contract::all(false).iter().all(|c| c.is_valid() && !c.hand));
+    }
+
+    #[test]
+    fn test_trump_set() {
+        let hearts = GameType::Suit(Suit::Heart);
+        assert_eq!(hearts.trump_set().len(), 11);
+        assert_eq!(hearts.suit_set(Suit::Club).len(), 7);
+        assert!(hearts.suit_set(Suit::Heart).is_empty());
+        assert_eq!(GameType::Null.suit_set(Suit::Club).len(), 8);
+        let jack = Card {
+            suit: Suit::Spade,
+            rank: Rank::Jack,
+        };
+        assert_eq!(hearts.follow_set(jack), hearts.trump_set());
+        assert_eq!(GameType::Null.follow_set(jack), CardSet::suit(Suit::Spade));
+        for game in GameType::all().iter() {
+            assert_eq!(game.trump_set(), game.trumps().iter().collect());
+        }
+    }
+}
@@ -1,42 +1,160 @@
-use crate::card::{Card, Rank, Suit};
+use crate::card::{Card, RANKS, SUITS};
+use crate::error::RoundError;
+use crate::game::{Round, RoundBuilder};
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::vec::Vec;
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// cards each player gets per round of dealing, the skat comes after the first
+const PACKETS: [usize; 3] = [3, 4, 3];
 
 pub struct Deck {
     pub cards: Vec<Card>,
 }
 
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
+    }
+}
+
 impl Deck {
     // returns an unshuffled deck
     pub fn new() -> Deck {
-        let ranks = [
-            Rank::Seven,
-            Rank::Eight,
-            Rank::Nine,
-            Rank::Queen,
-            Rank::King,
-            Rank::Ten,
-            Rank::Ace,
-            Rank::Jack,
-        ];
-
-        let suits = [Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond];
-
         let mut deck = Vec::new();
 
-        for s in suits.iter().cloned() {
-            for r in ranks.iter().cloned() {
+        for s in SUITS.iter().cloned() {
+            for r in RANKS.iter().cloned() {
                 deck.push(Card { suit: s, rank: r })
             }
         }
 
-        // deck.shuffle(&mut thread_rng());
-
         Deck { cards: deck }
     }
 
+    pub fn iter(&self) -> std::slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+
     pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut thread_rng());
+        self.shuffle_with_rng(&mut thread_rng());
+    }
+
+    pub fn shuffle_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    /// Shuffle with a seed, the same seed always gives the same order. This
+    /// uses ChaCha8 and its own Fisher-Yates rather than `StdRng` and
+    /// `SliceRandom::shuffle` which rand doesn't promise to keep stable.
+    pub fn shuffle_from_seed(&mut self, seed: u64) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        for i in (1..self.cards.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            self.cards.swap(i, j);
+        }
+    }
+
+    /// Cut the deck, the top `at` cards go to the bottom.
+    pub fn cut(&mut self, at: usize) {
+        let at = at % self.cards.len().max(1);
+        self.cards.rotate_left(at);
+    }
+
+    /// Deal the hands from the top of the deck the traditional way, packets
+    /// of 3, 4 and 3 starting with forehand and two cards into the skat
+    /// after the first round. Returns the hands indexed by player and the
+    /// skat.
+    pub fn deal(&self) -> ([Vec<Card>; 3], Vec<Card>) {
+        let mut hands = [Vec::new(), Vec::new(), Vec::new()];
+        let mut skat = Vec::new();
+        let mut cards = self.cards.iter().copied();
+
+        for (i, size) in PACKETS.iter().enumerate() {
+            for hand in hands.iter_mut() {
+                hand.extend(cards.by_ref().take(*size));
+            }
+            if i == 0 {
+                skat.extend(cards.by_ref().take(2));
+            }
+        }
+
+        (hands, skat)
+    }
+
+    /// Deal a new round, fails if the deck isn't a full skat deck.
+    pub fn deal_round(&self) -> Result<Round, RoundError> {
+        let (hands, skat) = self.deal();
+        RoundBuilder::new(&hands[0], &hands[1], &hands[2], &skat).build()
+    }
+}
+
+impl IntoIterator for Deck {
+    type Item = Card;
+    type IntoIter = std::vec::IntoIter<Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a Card;
+    type IntoIter = std::slice::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::format_hand;
+    use crate::seat::Seat;
+
+    #[test]
+    fn test_deal() {
+        let deck = Deck::new();
+        let (hands, skat) = deck.deal();
+        assert_eq!(hands[0][..3], deck.cards[..3]);
+        assert_eq!(hands[2][..3], deck.cards[6..9]);
+        assert_eq!(skat, deck.cards[9..11]);
+        assert_eq!(hands[0][3..7], deck.cards[11..15]);
+        assert_eq!(hands[2][7..], deck.cards[29..]);
+
+        let round = deck.deal_round().unwrap();
+        assert_eq!(round.hand(Seat::Middlehand), hands[1]);
+        assert!(round.validate().is_ok());
+
+        let mut short = Deck::new();
+        short.cards.pop();
+        assert_eq!(
+            short.deal_round(),
+            Err(RoundError::WrongHandSize(Seat::Rearhand))
+        );
+    }
+
+    #[test]
+    fn test_shuffle_from_seed() {
+        let mut deck = Deck::new();
+        deck.shuffle_from_seed(1234);
+        let (hands, skat) = deck.deal();
+        // recorded deals depend on this staying the same
+        assert_eq!(format_hand(&hands[0]), "D7 D8 CQ H8 HA SQ HQ H7 C8 DA");
+        assert_eq!(format_hand(&hands[1]), "SA ST S8 S9 HK SK CT DQ C9 DK");
+        assert_eq!(format_hand(&hands[2]), "DJ C7 SJ CK D9 CJ H9 HJ CA DT");
+        assert_eq!(format_hand(&skat), "S7 HT");
+    }
+
+    #[test]
+    fn test_cut() {
+        let mut deck = Deck::new();
+        deck.cut(30);
+        assert_eq!(deck.cards[0], Deck::new().cards[30]);
+        assert_eq!(deck.cards[2], Deck::new().cards[0]);
+        assert_eq!(deck.iter().count(), 32);
+        deck.cut(32);
+        assert_eq!(deck.cards[0], Deck::new().cards[30]);
     }
 }
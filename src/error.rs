@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::card::Card;
+use crate::contract::Contract;
+use crate::game::Mode;
+use crate::seat::Seat;
+
+/// Why an action couldn't be applied to a round.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum ActionError {
+    NotYourTurn,
+    // the action doesn't belong in the current phase of the round
+    WrongPhase(Mode),
+    IllegalBid(u16),
+    SkatNotDecided,
+    SkatAlreadyDecided,
+    IllegalContract(Contract),
+    CardNotInHand(Card),
+    DuplicateCard(Card),
+    WrongCardCount(usize),
+    MustFollowSuit(Card),
+    CannotDouble,
+    InvalidRound(RoundError),
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionError::NotYourTurn => write!(f, "It's not your turn."),
+            ActionError::WrongPhase(mode) => {
+                write!(f, "That can't be done in the {} phase.", mode)
+            }
+            ActionError::IllegalBid(bid) => write!(f, "{} isn't a bid you can make now.", bid),
+            ActionError::SkatNotDecided => {
+                write!(f, "Pick up the skat or play hand before announcing.")
+            }
+            ActionError::SkatAlreadyDecided => write!(f, "The skat has already been decided."),
+            ActionError::IllegalContract(contract) => {
+                write!(f, "{} can't be announced.", contract)
+            }
+            ActionError::CardNotInHand(card) => write!(f, "{} isn't in your hand.", card),
+            ActionError::DuplicateCard(card) => write!(f, "{} was given twice.", card),
+            ActionError::WrongCardCount(n) => write!(f, "Play one card at a time, not {}.", n),
+            ActionError::MustFollowSuit(card) => {
+                write!(f, "{} can't be played, you must follow suit.", card)
+            }
+            ActionError::CannotDouble => write!(f, "It's too late to double."),
+            ActionError::InvalidRound(err) => write!(f, "The round is broken: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+/// Why a round failed validation, usually because it was built or
+/// deserialized with inconsistent state.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum RoundError {
+    NoDeclarer,
+    NoContract,
+    DuplicateCard(Card),
+    MissingCards(usize),
+    // the player's held and played cards don't add up to 10
+    WrongHandSize(Seat),
+    WrongSkatSize(usize),
+    IllegalBid(u16),
+    BidsOutOfOrder,
+    IllegalContract(Contract),
+    // a card in one of the played tricks couldn't have been played
+    IllegalPlay(Card),
+    // the event at this position in a replayed log couldn't be applied
+    IllegalEvent(usize),
+}
+
+impl fmt::Display for RoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundError::NoDeclarer => write!(f, "nobody won the bidding."),
+            RoundError::NoContract => write!(f, "no game was announced."),
+            RoundError::DuplicateCard(card) => write!(f, "{} was dealt twice.", card),
+            RoundError::MissingCards(n) => write!(f, "{} cards are missing.", n),
+            RoundError::WrongHandSize(player) => {
+                write!(f, "{} wasn't dealt 10 cards.", player)
+            }
+            RoundError::WrongSkatSize(n) => write!(f, "the skat has {} cards.", n),
+            RoundError::IllegalBid(bid) => write!(f, "{} isn't a legal bid.", bid),
+            RoundError::BidsOutOfOrder => write!(f, "the bids are out of order."),
+            RoundError::IllegalContract(contract) => {
+                write!(f, "{} isn't a game that can be played.", contract)
+            }
+            RoundError::IllegalPlay(card) => write!(f, "{} couldn't have been played.", card),
+            RoundError::IllegalEvent(i) => write!(f, "event {} couldn't be replayed.", i),
+        }
+    }
+}
+
+impl std::error::Error for RoundError {}
+
+/// Why text couldn't be read as a card, see the `FromStr` impls in `card`.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum ParseCardError {
+    Empty,
+    UnknownSuit,
+    UnknownRank,
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCardError::Empty => write!(f, "no card was given."),
+            ParseCardError::UnknownSuit => write!(f, "that isn't a suit."),
+            ParseCardError::UnknownRank => write!(f, "that isn't a rank."),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// Why a table couldn't seat a player, deal or apply an action.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum TableError {
+    TableFull,
+    NotEnoughPlayers,
+    // the player isn't at the table or sits this deal out
+    NotPlaying(u32),
+    RoundInProgress,
+    NoRound,
+    Action(ActionError),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableError::TableFull => write!(f, "The table is full."),
+            TableError::NotEnoughPlayers => write!(f, "Wait for more players to join."),
+            TableError::NotPlaying(player) => write!(f, "Player {} isn't in this deal.", player),
+            TableError::RoundInProgress => write!(f, "Finish the current round first."),
+            TableError::NoRound => write!(f, "Nothing has been dealt yet."),
+            TableError::Action(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
@@ -1,110 +1,845 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::card::{Card, Rank, Suit};
+use crate::card::Card;
+use crate::cardset::CardSet;
+use crate::contract::{Contract, GameType};
+use crate::deck::Deck;
+use crate::error::{ActionError, RoundError};
+use crate::seat::Seat;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Round {
     state: State,
-    pub forehand: [Option<Card>; 10],
-    pub middlehand: [Option<Card>; 10],
-    pub rearhand: [Option<Card>; 10],
-    skat: [Option<Card>; 2],
+    forehand: CardSet,
+    middlehand: CardSet,
+    rearhand: CardSet,
+    // empty while the declarer holds it to discard
+    skat: CardSet,
+    // the cards played to the current trick, indexed by seat
     trick: [Option<Card>; 3],
+    tricks: Vec<Trick>,
+    // where the round started, with the rules and seed, and every action
+    // since, see `replay`
+    deal: Deal,
+    events: Vec<Event>,
+}
+
+/// Optional rules that vary from table to table.
+#[derive(Serialize, Deserialize, Copy, Clone, Default, PartialEq, Debug)]
+pub struct Rules {
+    // play a ramsch instead of throwing the deal away when everyone passes
+    pub ramsch: bool,
+    // allow the defenders to double with kontra and the declarer to redouble
+    pub kontra: bool,
+}
+
+/// A completed trick, the cards are indexed by seat.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub struct Trick {
+    pub cards: [Card; 3],
+    pub lead: Seat,
+    pub winner: Seat,
+}
+
+impl Trick {
+    /// The cards in the order they were played, starting with the lead.
+    pub fn play_order(&self) -> [Card; 3] {
+        let second = self.lead.next();
+        let third = second.next();
+        [
+            self.cards[self.lead.index()],
+            self.cards[second.index()],
+            self.cards[third.index()],
+        ]
+    }
+}
+
+/// Where a round started, what its events are replayed from. For a fresh
+/// deal that's the cards and the rules, a round set up with a `RoundBuilder`
+/// also starts with its bids, contract and any tricks already played. The
+/// declarer is whoever bid highest.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Deal {
+    // the declarer's hand includes the skat when starting to discard
+    pub hands: [CardSet; 3],
+    pub skat: CardSet,
+    pub rules: Rules,
+    pub mode: Mode,
+    pub turn: Seat,
+    pub bids: [u16; 3],
+    pub contract: Option<Contract>,
+    // whether the declarer plays without the skat, None until they've decided
+    pub hand: Option<bool>,
+    pub tricks: Vec<Trick>,
+    // what the deck was shuffled with so the deal can be repeated
+    pub seed: Option<u64>,
+}
+
+/// An action a player took, rounds keep these in the order they happened.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Event {
+    pub seat: Seat,
+    pub action: Action,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 struct State {
-    bids: [u8; 3],
+    bids: [u16; 3],
+    // 2 after kontra and 4 after re
     modifier: u8,
-    turn: u8,
+    // how many cards the declarer held when kontra was called, re has to come
+    // before they play another
+    kontra: Option<u8>,
+    turn: Seat,
     mode: Mode,
+    // whether the declarer plays without the skat, None until they've decided
+    hand: Option<bool>,
+    contract: Option<Contract>,
+    // the player who led the current trick
+    lead: Seat,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
 pub enum Mode {
-    SuitGame(Suit),
-    Null,
-    Grand,
     Bidding,
     Announcing,
+    Discarding,
+    Playing,
+    // every player passed so it's each for themselves with only jacks as
+    // trumps, the player with the most points loses
+    Ramsch,
     Finished,
 }
 
+/// The phase's name in lower case, like bidding.
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Bidding => write!(f, "bidding"),
+            Mode::Announcing => write!(f, "announcing"),
+            Mode::Discarding => write!(f, "discarding"),
+            Mode::Playing => write!(f, "playing"),
+            Mode::Ramsch => write!(f, "ramsch"),
+            Mode::Finished => write!(f, "finished"),
+        }
+    }
+}
+
+/// How a finished round turned out for the declarer.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct GameResult {
+    pub declarer: Seat,
+    pub contract: Contract,
+    pub won: bool,
+    pub bid: u16,
+    // card points of the declarer's tricks and the skat
+    pub points: u8,
+    pub value: u16,
+    // whether the game was worth less than the bid
+    pub overbid: bool,
+    // whether the losing side got 30 or fewer points
+    pub schneider: bool,
+    // whether the losing side took no tricks
+    pub schwarz: bool,
+    // 2 after kontra and 4 after re
+    pub modifier: u8,
+}
+
+impl GameResult {
+    fn new(
+        declarer: Seat,
+        contract: Contract,
+        bid: u16,
+        cards: &[Card],
+        points: u8,
+        declarer_tricks: usize,
+        defender_tricks: usize,
+    ) -> GameResult {
+        let mut result = if contract.game == GameType::Null {
+            GameResult {
+                declarer,
+                contract,
+                bid,
+                won: declarer_tricks == 0,
+                points,
+                value: contract.value(cards, false, false),
+                overbid: false,
+                schneider: false,
+                schwarz: false,
+                modifier: 1,
+            }
+        } else {
+            let schneider = points >= 90 || points <= 30;
+            let schwarz = declarer_tricks == 0 || defender_tricks == 0;
+            let won = if contract.schwarz {
+                defender_tricks == 0
+            } else if contract.schneider {
+                points >= 90
+            } else {
+                points > 60
+            };
+            GameResult {
+                declarer,
+                contract,
+                bid,
+                won,
+                points,
+                value: contract.value(cards, schneider, schwarz),
+                overbid: false,
+                schneider,
+                schwarz,
+                modifier: 1,
+            }
+        };
+
+        // A game worth less than the bid is lost at the lowest multiple of its
+        // base value that covers the bid. Schneider or schwarz reached during
+        // play count towards the value so can save a hand game.
+        if result.value < bid {
+            let base = match contract.game {
+                GameType::Null => result.value,
+                game => game.base_value(),
+            };
+            // rather than div_ceil which needs a newer Rust than the frontend
+            #[allow(clippy::manual_div_ceil)]
+            let value = (bid + base - 1) / base * base;
+            result.value = value;
+            result.won = false;
+            result.overbid = true;
+        }
+        result
+    }
+}
+
+/// How a finished ramsch turned out.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct RamschResult {
+    // card points indexed by seat, the skat goes to the last trick
+    pub points: [u8; 3],
+    // the players with the most points, usually just one
+    pub losers: Vec<Seat>,
+    // how many players took no tricks, each one doubles the loss
+    pub jungfrau: u8,
+    // the player who took every trick and wins instead
+    pub durchmarsch: Option<Seat>,
+}
+
+impl RamschResult {
+    fn new(points: [u8; 3], tricks: [usize; 3]) -> RamschResult {
+        let most = points.iter().max().copied().unwrap_or(0);
+        RamschResult {
+            points,
+            losers: Seat::all()
+                .iter()
+                .copied()
+                .filter(|seat| points[seat.index()] == most)
+                .collect(),
+            jungfrau: tricks.iter().filter(|t| **t == 0).count() as u8,
+            durchmarsch: Seat::all()
+                .iter()
+                .copied()
+                .find(|seat| tricks[seat.index()] == 10),
+        }
+    }
+}
+
+/// Everything one player is allowed to know about a round. This is what gets
+/// sent to clients and bots rather than the round itself.
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct RoundView {
+    pub player: Seat,
+    pub mode: Mode,
+    pub turn: Seat,
+    pub bids: [u16; 3],
+    pub declarer: Option<Seat>,
+    pub contract: Option<Contract>,
+    pub modifier: u8,
+    pub hand: Vec<Card>,
+    // how many cards each player holds
+    pub hand_sizes: [usize; 3],
+    // the declarer's cards when playing ouvert
+    pub open_hand: Option<Vec<Card>>,
+    pub skat_size: usize,
+    // the skat once this player has seen it
+    pub skat: Option<Vec<Card>>,
+    pub trick: [Option<Card>; 3],
+    pub tricks: Vec<Trick>,
+    pub actions: Vec<Action>,
+}
+
+/// What changed after an action was applied.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum Outcome {
+    NextTurn(Seat),
+    TrickCompleted { winner: Seat },
+    PhaseChanged(Mode),
+    // kontra or re with the new modifier
+    Doubled(u8),
+    RoundFinished,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Action {
-    Bid(u8),
+    Bid(u16),
     Pass,
     TakeSkat,
-    ReturnSkat,
-    Announce(Mode),
+    PlayHand,
+    ReturnSkat([Card; 2]),
+    Announce(Contract),
     Cards(Vec<Card>),
+    Kontra,
+    Re,
 }
 
 pub fn new_round() -> Round {
-    let ranks = [
-        Rank::Seven,
-        Rank::Eight,
-        Rank::Nine,
-        Rank::Queen,
-        Rank::King,
-        Rank::Ten,
-        Rank::Ace,
-        Rank::Jack,
-    ];
+    new_round_with_rng(&mut thread_rng())
+}
+
+/// Deal a round with a seed drawn from the given generator.
+pub fn new_round_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Round {
+    new_round_from_seed(rng.gen())
+}
 
-    let suits = [Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond];
+/// Deal a round from a seed, the same seed always gives the same deal.
+pub fn new_round_from_seed(seed: u64) -> Round {
+    let mut deck = Deck::new();
+    deck.shuffle_from_seed(seed);
 
-    let mut deck = Vec::new();
+    let mut round = deck
+        .deal_round()
+        .expect("a full deck always deals a valid round");
+    round.deal.seed = Some(seed);
+    round
+}
+
+/// Sets up a round from a known deal, for puzzles, test fixtures or
+/// replaying a game that was played elsewhere.
+///
+/// The hands are what each player held when play began, so after the
+/// declarer picked up the skat, and the skat is what was left in it. Any
+/// played tricks are replayed in order through `apply_action` but aren't
+/// logged as events, they're part of the round's `Deal`.
+pub struct RoundBuilder {
+    hands: [Vec<Card>; 3],
+    skat: Vec<Card>,
+    mode: Option<Mode>,
+    turn: Option<Seat>,
+    bids: [u16; 3],
+    contract: Option<Contract>,
+    // the cards of each trick in the order they were played
+    tricks: Vec<[Card; 3]>,
+    rules: Rules,
+}
 
-    for s in suits.iter() {
-        for r in ranks.iter() {
-            deck.push(Some(Card { suit: *s, rank: *r }))
+impl RoundBuilder {
+    pub fn new(
+        forehand: &[Card],
+        middlehand: &[Card],
+        rearhand: &[Card],
+        skat: &[Card],
+    ) -> RoundBuilder {
+        RoundBuilder {
+            hands: [forehand.to_vec(), middlehand.to_vec(), rearhand.to_vec()],
+            skat: skat.to_vec(),
+            mode: None,
+            turn: None,
+            bids: [0; 3],
+            contract: None,
+            tricks: Vec::new(),
+            rules: Rules::default(),
         }
     }
 
-    deck.shuffle(&mut thread_rng());
+    /// The phase to start in, by default bidding or playing once a contract
+    /// is set. Ignored when tricks are given.
+    pub fn mode(mut self, mode: Mode) -> RoundBuilder {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Whose turn it is, by default whoever would be first to act in the
+    /// phase. Ignored when tricks are given.
+    pub fn turn(mut self, player: Seat) -> RoundBuilder {
+        self.turn = Some(player);
+        self
+    }
 
-    let state = State {
-        bids: [0; 3],
-        modifier: 1,
-        turn: 1,
-        mode: Mode::Bidding,
-    };
+    pub fn bids(mut self, bids: [u16; 3]) -> RoundBuilder {
+        self.bids = bids;
+        self
+    }
 
-    let mut round = Round {
-        state: state,
-        forehand: [None; 10],
-        middlehand: [None; 10],
-        rearhand: [None; 10],
-        skat: [None; 2],
-        trick: [None; 3],
-    };
+    pub fn contract(mut self, contract: Contract) -> RoundBuilder {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Tricks already played, each in the order the cards hit the table rather
+    /// than by seat, see `Trick::play_order`. They are played as a ramsch when
+    /// there's no contract.
+    pub fn tricks(mut self, tricks: &[[Card; 3]]) -> RoundBuilder {
+        self.tricks = tricks.to_vec();
+        self
+    }
+
+    pub fn rules(mut self, rules: Rules) -> RoundBuilder {
+        self.rules = rules;
+        self
+    }
+
+    /// Build and validate the round, every card has to be dealt exactly once
+    /// and the tricks have to be legal plays.
+    pub fn build(self) -> Result<Round, RoundError> {
+        let mode = if !self.tricks.is_empty() {
+            if self.contract.is_some() {
+                Mode::Playing
+            } else {
+                Mode::Ramsch
+            }
+        } else {
+            self.mode.unwrap_or(if self.contract.is_some() {
+                Mode::Playing
+            } else {
+                Mode::Bidding
+            })
+        };
+
+        let mut round = Round::from_deal(Deal {
+            hands: [CardSet::new(); 3],
+            skat: CardSet::new(),
+            rules: self.rules,
+            mode,
+            turn: Seat::Forehand,
+            bids: self.bids,
+            contract: self.contract,
+            hand: self.contract.map(|contract| contract.hand),
+            tricks: Vec::new(),
+            seed: None,
+        });
 
-    let mut iter = deck.chunks_exact(10);
+        for (player, hand) in Seat::all().iter().zip(self.hands.iter()) {
+            if hand.len() != 10 {
+                return Err(RoundError::WrongHandSize(*player));
+            }
+            for card in hand.iter() {
+                if !round.seat_mut(*player).insert(*card) {
+                    return Err(RoundError::DuplicateCard(*card));
+                }
+            }
+        }
+        if self.skat.len() > 2 {
+            return Err(RoundError::WrongSkatSize(self.skat.len()));
+        }
+        for card in self.skat.iter() {
+            if !round.skat.insert(*card) {
+                return Err(RoundError::DuplicateCard(*card));
+            }
+        }
+
+        if mode == Mode::Discarding {
+            // the declarer has picked up the skat
+            round.state.hand = Some(false);
+            if let Some(declarer) = round.declarer() {
+                let skat = round.skat;
+                *round.seat_mut(declarer) |= skat;
+                round.skat = CardSet::new();
+            }
+        }
+        let first = match mode {
+            Mode::Bidding => Seat::Middlehand,
+            Mode::Announcing | Mode::Discarding => round.declarer().unwrap_or(Seat::Forehand),
+            _ => Seat::Forehand,
+        };
+        round.state.turn = match self.turn {
+            Some(player) if self.tricks.is_empty() => player,
+            _ => first,
+        };
+        if let Some(contract) = self.contract {
+            if !contract.is_valid() {
+                return Err(RoundError::IllegalContract(contract));
+            }
+        }
+        round.validate()?;
 
-    round.forehand.copy_from_slice(iter.next().unwrap());
-    round.middlehand.copy_from_slice(iter.next().unwrap());
-    round.rearhand.copy_from_slice(iter.next().unwrap());
-    round.skat.copy_from_slice(iter.remainder());
+        for trick in self.tricks.iter() {
+            for card in trick.iter() {
+                let player = round.state.turn;
+                apply(&mut round, Action::Cards(vec![*card]), player)
+                    .map_err(|_| RoundError::IllegalPlay(*card))?;
+            }
+        }
+        round.deal = round.starting_deal();
 
-    return round;
+        Ok(round)
+    }
 }
 
-pub fn available_actions(round: &Round, player: u8) -> Option<Vec<Action>> {
-    if player != round.state.turn {
-        return None;
+impl Round {
+    pub fn mode(&self) -> Mode {
+        self.state.mode
+    }
+
+    /// The seed the round was dealt from, see `new_round_from_seed`.
+    pub fn seed(&self) -> Option<u64> {
+        self.deal.seed
+    }
+
+    /// Where the round started, see `replay`.
+    pub fn deal(&self) -> &Deal {
+        &self.deal
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.deal.rules
+    }
+
+    /// Change the rules before anyone has acted. Returns false once the round
+    /// is under way, the rules are fixed from then on.
+    pub fn set_rules(&mut self, rules: Rules) -> bool {
+        if !self.events.is_empty() {
+            return false;
+        }
+        self.deal.rules = rules;
+        true
+    }
+
+    /// Every action applied to the round so far in order.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Rebuild a round by applying the events to where it started, e.g. the
+    /// state after the first `n` actions is
+    /// `Round::replay(round.deal(), &round.events()[..n])`.
+    pub fn replay(deal: &Deal, events: &[Event]) -> Result<Round, RoundError> {
+        let mut round = Round::from_deal(deal.clone());
+        round.validate()?;
+        for (i, event) in events.iter().enumerate() {
+            apply_action(&mut round, event.action.clone(), event.seat)
+                .map_err(|_| RoundError::IllegalEvent(i))?;
+        }
+        Ok(round)
+    }
+
+    fn from_deal(deal: Deal) -> Round {
+        Round {
+            state: State {
+                bids: deal.bids,
+                modifier: 1,
+                kontra: None,
+                turn: deal.turn,
+                mode: deal.mode,
+                hand: deal.hand,
+                contract: deal.contract,
+                lead: deal
+                    .tricks
+                    .last()
+                    .map_or(Seat::Forehand, |trick| trick.winner),
+            },
+            forehand: deal.hands[0],
+            middlehand: deal.hands[1],
+            rearhand: deal.hands[2],
+            skat: deal.skat,
+            trick: [None; 3],
+            tricks: deal.tricks.clone(),
+            deal,
+            events: Vec::new(),
+        }
+    }
+
+    // The round as it stands as somewhere to replay from, only right before
+    // anyone has acted.
+    fn starting_deal(&self) -> Deal {
+        Deal {
+            hands: [self.forehand, self.middlehand, self.rearhand],
+            skat: self.skat,
+            rules: self.deal.rules,
+            mode: self.state.mode,
+            turn: self.state.turn,
+            bids: self.state.bids,
+            contract: self.state.contract,
+            hand: self.state.hand,
+            tricks: self.tricks.clone(),
+            seed: self.deal.seed,
+        }
+    }
+
+    /// The round as seen by one player. Other hands and the skat are only
+    /// counted, except an ouvert declarer's hand which everyone sees.
+    pub fn view_for(&self, player: Seat) -> RoundView {
+        let declarer = self.declarer();
+        let contract = self.state.contract;
+        let mut hand_sizes = [0; 3];
+        for (seat, size) in Seat::all().iter().zip(hand_sizes.iter_mut()) {
+            *size = self.seat(*seat).len();
+        }
+
+        let open_hand = match (declarer, contract) {
+            (Some(declarer), Some(contract)) if contract.ouvert => Some(self.hand(declarer)),
+            _ => None,
+        };
+        // the declarer knows what they put back and everyone can look at the
+        // end
+        let seen_skat = self.state.mode == Mode::Finished
+            || (declarer == Some(player) && self.state.hand == Some(false));
+        let skat: Vec<Card> = self.skat.iter().collect();
+        let skat_size = skat.len();
+
+        RoundView {
+            player,
+            mode: self.state.mode,
+            turn: self.state.turn,
+            bids: self.state.bids,
+            declarer,
+            contract,
+            modifier: self.state.modifier,
+            hand: self.hand(player),
+            hand_sizes,
+            open_hand,
+            skat_size,
+            skat: if seen_skat { Some(skat) } else { None },
+            trick: self.trick,
+            tricks: self.tricks.clone(),
+            actions: available_actions(self, player).unwrap_or_default(),
+        }
+    }
+
+    /// Skip the bidding and play the deal as a ramsch, e.g. during a ramsch
+    /// series. Returns false once the bidding has started.
+    pub fn play_ramsch(&mut self) -> bool {
+        if self.state.mode != Mode::Bidding
+            || self.state.bids != [0; 3]
+            || self.state.turn != Seat::Middlehand
+        {
+            return false;
+        }
+        self.state.mode = Mode::Ramsch;
+        self.state.turn = Seat::Forehand;
+        self.deal = self.starting_deal();
+        true
+    }
+
+    /// The cards currently held by a player. While discarding this includes
+    /// the two skat cards picked up by the declarer.
+    pub fn hand(&self, player: Seat) -> Vec<Card> {
+        self.seat(player).iter().collect()
+    }
+
+    /// The cards currently held by a player as a set.
+    pub fn hand_set(&self, player: Seat) -> CardSet {
+        *self.seat(player)
+    }
+
+    pub fn skat(&self) -> CardSet {
+        self.skat
+    }
+
+    /// The player who won the bidding, the highest bid is held by the
+    /// earliest seat.
+    pub fn declarer(&self) -> Option<Seat> {
+        let max_bid = self.state.bids.iter().max()?;
+        if *max_bid == 0 {
+            return None;
+        }
+        self.state
+            .bids
+            .iter()
+            .position(|bid| bid == max_bid)
+            .and_then(Seat::from_index)
+    }
+
+    /// The tricks won by the declarer.
+    pub fn declarer_tricks(&self) -> Vec<&Trick> {
+        let declarer = self.declarer();
+        self.tricks
+            .iter()
+            .filter(|trick| Some(trick.winner) == declarer)
+            .collect()
+    }
+
+    /// The tricks won by the two defenders.
+    pub fn defender_tricks(&self) -> Vec<&Trick> {
+        let declarer = self.declarer();
+        self.tricks
+            .iter()
+            .filter(|trick| Some(trick.winner) != declarer)
+            .collect()
+    }
+
+    /// The outcome of a finished round, None if it isn't finished or was
+    /// passed in.
+    pub fn result(&self) -> Option<GameResult> {
+        if self.state.mode != Mode::Finished {
+            return None;
+        }
+        let contract = self.state.contract?;
+        let declarer = self.declarer()?;
+        let tricks = self.declarer_tricks();
+        let won: CardSet = tricks.iter().flat_map(|trick| trick.cards.iter()).collect();
+        let points = (won | self.skat).points();
+        // the declarer's 12 cards as they were after the skat was decided
+        let mut cards = *self.seat(declarer) | self.skat;
+        cards.extend(
+            self.tricks
+                .iter()
+                .map(|trick| trick.cards[declarer.index()]),
+        );
+        let cards: Vec<Card> = cards.iter().collect();
+        let mut result = GameResult::new(
+            declarer,
+            contract,
+            self.state.bids[declarer.index()],
+            &cards,
+            points,
+            tricks.len(),
+            self.defender_tricks().len(),
+        );
+        result.modifier = self.state.modifier;
+        Some(result)
+    }
+
+    /// The outcome of a finished ramsch, None if it isn't finished or
+    /// wasn't a ramsch.
+    pub fn ramsch_result(&self) -> Option<RamschResult> {
+        if self.state.mode != Mode::Finished || self.state.contract.is_some() {
+            return None;
+        }
+        let last = self.tricks.last()?;
+        let mut points = [0; 3];
+        let mut tricks = [0; 3];
+        for trick in self.tricks.iter() {
+            let winner = trick.winner.index();
+            points[winner] += trick.cards.iter().map(Card::points).sum::<u8>();
+            tricks[winner] += 1;
+        }
+        points[last.winner.index()] += self.skat.points();
+        Some(RamschResult::new(points, tricks))
+    }
+
+    /// Check the round is consistent, e.g. after deserializing it. Every card
+    /// must be somewhere exactly once, each player must have held 10 cards
+    /// and the bids must be in order.
+    pub fn validate(&self) -> Result<(), RoundError> {
+        self.check_state()?;
+
+        let discarding = self.state.mode == Mode::Discarding;
+        let mut cards = CardSet::new();
+        for player in Seat::all().iter().copied() {
+            let held = self.seat(player).len();
+            let played = self.tricks.len() + self.trick[player.index()].iter().count();
+            let picked_up = if discarding && self.declarer() == Some(player) {
+                2
+            } else {
+                0
+            };
+            if held + played != 10 + picked_up {
+                return Err(RoundError::WrongHandSize(player));
+            }
+            if let Some(card) = (cards & *self.seat(player)).iter().next() {
+                return Err(RoundError::DuplicateCard(card));
+            }
+            cards |= *self.seat(player);
+        }
+        let placed = self
+            .skat
+            .iter()
+            .chain(self.trick.iter().flatten().copied())
+            .chain(
+                self.tricks
+                    .iter()
+                    .flat_map(|trick| trick.cards.iter().copied()),
+            );
+        for card in placed {
+            if !cards.insert(card) {
+                return Err(RoundError::DuplicateCard(card));
+            }
+        }
+        if cards.len() != 32 {
+            return Err(RoundError::MissingCards(32 - cards.len()));
+        }
+
+        let bids = self.state.bids;
+        if let Some(bid) = bids.iter().find(|bid| **bid != 0 && !is_legal_bid(**bid)) {
+            return Err(RoundError::IllegalBid(*bid));
+        }
+        // forehand only ever holds middlehand's bid and rearhand has to at
+        // least match whoever is left
+        let in_order = if bids[2] == 0 {
+            bids[1] == 0 || bids[0] <= bids[1]
+        } else {
+            bids[0] <= bids[2] && bids[1] <= bids[2]
+        };
+        if !in_order {
+            return Err(RoundError::BidsOutOfOrder);
+        }
+        Ok(())
+    }
+
+    // The checks the state machine relies on to find the declarer and the
+    // game.
+    fn check_state(&self) -> Result<(), RoundError> {
+        let state = &self.state;
+        let declarer = self.declarer();
+        match state.mode {
+            Mode::Announcing | Mode::Discarding | Mode::Playing if declarer.is_none() => {
+                Err(RoundError::NoDeclarer)
+            }
+            Mode::Playing if state.contract.is_none() => Err(RoundError::NoContract),
+            _ => Ok(()),
+        }
+    }
+
+    // The game whose trumps and card order are used during play.
+    fn game_type(&self) -> Option<GameType> {
+        match self.state.mode {
+            Mode::Playing => self.state.contract.map(|contract| contract.game),
+            Mode::Ramsch => Some(GameType::Grand),
+            _ => None,
+        }
+    }
+
+    fn seat(&self, player: Seat) -> &CardSet {
+        match player {
+            Seat::Forehand => &self.forehand,
+            Seat::Middlehand => &self.middlehand,
+            Seat::Rearhand => &self.rearhand,
+        }
     }
 
+    fn seat_mut(&mut self, player: Seat) -> &mut CardSet {
+        match player {
+            Seat::Forehand => &mut self.forehand,
+            Seat::Middlehand => &mut self.middlehand,
+            Seat::Rearhand => &mut self.rearhand,
+        }
+    }
+}
+
+pub fn available_actions(round: &Round, player: Seat) -> Option<Vec<Action>> {
+    round.check_state().ok()?;
+
+    // kontra and re don't have to wait for the player's turn
     let mut actions = Vec::new();
+    if let Some(action) = get_doubling(round, player) {
+        actions.push(action);
+    }
+    if player != round.state.turn {
+        return if actions.is_empty() {
+            None
+        } else {
+            Some(actions)
+        };
+    }
+
     match round.state.mode {
         Mode::Bidding => {
             let max_bid = round.state.bids.iter().max().unwrap();
             let next_bid = get_next_bid(max_bid);
-            let player_bid = round.state.bids[round.state.turn as usize];
-            let bid: u8;
-            if player_bid == 0 && round.state.turn == 2 {
+            let player_bid = round.state.bids[round.state.turn.index()];
+            let bid: Option<u16>;
+            if player_bid == 0 && round.state.turn == Seat::Rearhand {
                 // Special case for rear's 1st bid
                 bid = next_bid;
             } else if player_bid == *max_bid {
@@ -112,12 +847,53 @@ pub fn available_actions(round: &Round, player: u8) -> Option<Vec<Action>> {
                 bid = next_bid;
             } else {
                 // player must match
-                bid = *max_bid;
+                bid = Some(*max_bid);
             }
 
-            actions.push(Action::Bid(bid));
+            // nothing to raise to once the highest game value has been bid
+            if let Some(bid) = bid {
+                actions.push(Action::Bid(bid));
+            }
             actions.push(Action::Pass);
         }
+        Mode::Announcing => match round.state.hand {
+            None => {
+                actions.push(Action::TakeSkat);
+                actions.push(Action::PlayHand);
+            }
+            Some(hand) => {
+                let bid = round.state.bids[player.index()];
+                for contract in Contract::all(hand) {
+                    // a null's value is known up front so it can't be overbid
+                    if contract.game == GameType::Null && contract.value(&[], false, false) < bid {
+                        continue;
+                    }
+                    actions.push(Action::Announce(contract));
+                }
+            }
+        },
+        Mode::Playing | Mode::Ramsch => {
+            let game = round.game_type()?;
+            let cards = *round.seat(player);
+            let playable = match round.trick[round.state.lead.index()] {
+                // must follow suit
+                Some(lead) if !(cards & game.follow_set(lead)).is_empty() => {
+                    cards & game.follow_set(lead)
+                }
+                _ => cards,
+            };
+            for card in playable {
+                actions.push(Action::Cards(vec![card]));
+            }
+        }
+        Mode::Discarding => {
+            let cards = round.hand(player);
+            for (i, a) in cards.iter().enumerate() {
+                for b in cards[i + 1..].iter() {
+                    actions.push(Action::ReturnSkat([*a, *b]));
+                }
+            }
+        }
         _ => {
             return None;
         }
@@ -125,32 +901,109 @@ pub fn available_actions(round: &Round, player: u8) -> Option<Vec<Action>> {
     return Some(actions);
 }
 
-pub fn apply_action(round: &mut Round, action: Action, player: u8) -> bool {
-    match available_actions(round, player) {
-        None => {
-            return false;
+// Kontra is open to a defender until they play their first card and re to the
+// declarer until they play their next card after the kontra.
+fn get_doubling(round: &Round, player: Seat) -> Option<Action> {
+    if !round.rules().kontra || round.state.mode != Mode::Playing {
+        return None;
+    }
+    let cards = round.seat(player).len() as u8;
+    let declarer = round.declarer()?;
+    match round.state.kontra {
+        None if player != declarer && cards == 10 => Some(Action::Kontra),
+        Some(n) if player == declarer && cards == n && round.state.modifier == 2 => {
+            Some(Action::Re)
         }
-        Some(aa) => {
-            if !(aa.contains(&action)) {
-                return false;
-            }
+        _ => None,
+    }
+}
+
+// Check an action is available to the player and if not work out why.
+fn check_action(round: &Round, action: &Action, player: Seat) -> Result<(), ActionError> {
+    round.check_state().map_err(ActionError::InvalidRound)?;
+    if let Action::Kontra | Action::Re = action {
+        return match get_doubling(round, player) {
+            Some(ref doubling) if doubling == action => Ok(()),
+            _ => Err(ActionError::CannotDouble),
+        };
+    }
+    if player != round.state.turn {
+        return Err(ActionError::NotYourTurn);
+    }
+
+    let available = available_actions(round, player).unwrap_or_default();
+    let allowed = match action {
+        // the skat can be returned in either order
+        Action::ReturnSkat([a, b]) => {
+            available.contains(action) || available.contains(&Action::ReturnSkat([*b, *a]))
         }
+        _ => available.contains(action),
+    };
+    if allowed {
+        return Ok(());
     }
 
+    let hand = round.hand(player);
+    Err(match (round.state.mode, action) {
+        (Mode::Bidding, Action::Bid(bid)) => ActionError::IllegalBid(*bid),
+        (Mode::Announcing, Action::TakeSkat) | (Mode::Announcing, Action::PlayHand) => {
+            ActionError::SkatAlreadyDecided
+        }
+        (Mode::Announcing, Action::Announce(_)) if round.state.hand.is_none() => {
+            ActionError::SkatNotDecided
+        }
+        (Mode::Announcing, Action::Announce(contract)) => ActionError::IllegalContract(*contract),
+        (Mode::Discarding, Action::ReturnSkat(cards)) => {
+            match cards.iter().find(|card| !hand.contains(card)) {
+                Some(card) => ActionError::CardNotInHand(*card),
+                None => ActionError::DuplicateCard(cards[0]),
+            }
+        }
+        (Mode::Playing, Action::Cards(cards)) | (Mode::Ramsch, Action::Cards(cards)) => {
+            match cards.as_slice() {
+                [card] if !hand.contains(card) => ActionError::CardNotInHand(*card),
+                [card] => ActionError::MustFollowSuit(*card),
+                _ => ActionError::WrongCardCount(cards.len()),
+            }
+        }
+        (mode, _) => ActionError::WrongPhase(mode),
+    })
+}
+
+/// Apply a player's action and add it to the round's events.
+pub fn apply_action(
+    round: &mut Round,
+    action: Action,
+    player: Seat,
+) -> Result<Outcome, ActionError> {
+    let event = Event {
+        seat: player,
+        action: action.clone(),
+    };
+    let outcome = apply(round, action, player)?;
+    round.events.push(event);
+    Ok(outcome)
+}
+
+fn apply(round: &mut Round, action: Action, player: Seat) -> Result<Outcome, ActionError> {
+    check_action(round, &action, player)?;
+
+    let mode = round.state.mode;
+    let tricks = round.tricks.len();
     let turn = round.state.turn;
     match action {
         Action::Pass => {
             let bids = &round.state.bids;
             if bids == &[0, 0, 0] {
                 match turn {
-                    1 => round.state.turn = 2,
-                    2 => round.state.turn = 0,
-                    0 => round.state.mode = Mode::Finished, // everyone passed
-                    _ => panic!(),
+                    Seat::Middlehand | Seat::Rearhand => round.state.turn = turn.next(),
+                    // forehand passes last so everyone passed
+                    Seat::Forehand if round.rules().ramsch => round.state.mode = Mode::Ramsch,
+                    Seat::Forehand => round.state.mode = Mode::Finished,
                 }
-            } else if turn == 0 || turn == 1 {
+            } else if turn != Seat::Rearhand {
                 // when fore/middle passes rear either won or hasn't bid yet
-                round.state.turn = 2;
+                round.state.turn = Seat::Rearhand;
                 if round.state.bids[2] != 0 {
                     // rear has bid therefore rear won
                     round.state.mode = Mode::Announcing;
@@ -159,60 +1012,197 @@ pub fn apply_action(round: &mut Round, action: Action, player: u8) -> bool {
                 // when rear passes fore or middle won bidding
                 round.state.mode = Mode::Announcing;
                 if bids[0] < bids[1] {
-                    round.state.turn = 1
+                    round.state.turn = Seat::Middlehand
                 } else {
-                    round.state.turn = 0
+                    round.state.turn = Seat::Forehand
                 }
             }
         }
         Action::Bid(n) => {
-            round.state.bids[turn as usize] = n;
-            round.state.turn = get_next_bidder(&round.state.bids);
-            return true;
+            let mut bids = round.state.bids;
+            bids[turn.index()] = n;
+            if bids[1..] == [0, 0] {
+                // middle and rear passed so forehand wins with their bid
+                round.state.mode = Mode::Announcing;
+            } else {
+                round.state.turn = get_next_bidder(&bids)
+                    .ok_or(ActionError::InvalidRound(RoundError::BidsOutOfOrder))?;
+            }
+            round.state.bids = bids;
+        }
+        Action::TakeSkat => {
+            let skat = round.skat;
+            *round.seat_mut(turn) |= skat;
+            round.skat = CardSet::new();
+            round.state.hand = Some(false);
+            round.state.mode = Mode::Discarding;
+        }
+        Action::PlayHand => {
+            round.state.hand = Some(true);
+        }
+        Action::ReturnSkat(discards) => {
+            let discards: CardSet = discards.iter().collect();
+            *round.seat_mut(turn) -= discards;
+            round.skat = discards;
+            round.state.mode = Mode::Announcing;
+        }
+        Action::Announce(contract) => {
+            round.state.contract = Some(contract);
+            round.state.mode = Mode::Playing;
+            // forehand always leads the first trick
+            round.state.turn = Seat::Forehand;
+            round.state.lead = Seat::Forehand;
+        }
+        Action::Kontra => {
+            let declarer = round
+                .declarer()
+                .ok_or(ActionError::InvalidRound(RoundError::NoDeclarer))?;
+            round.state.modifier = 2;
+            round.state.kontra = Some(round.seat(declarer).len() as u8);
+            return Ok(Outcome::Doubled(2));
+        }
+        Action::Re => {
+            round.state.modifier = 4;
+            return Ok(Outcome::Doubled(4));
+        }
+        Action::Cards(cards) => {
+            let card = cards[0];
+            round.seat_mut(turn).remove(card);
+            round.trick[turn.index()] = Some(card);
+            round.state.turn = turn.next();
+
+            if let [Some(a), Some(b), Some(c)] = round.trick {
+                let cards = [a, b, c];
+                let lead = round.state.lead;
+                let game = round
+                    .game_type()
+                    .ok_or(ActionError::InvalidRound(RoundError::NoContract))?;
+                let winner = game.trick_winner(&cards, lead);
+                round.tricks.push(Trick {
+                    cards,
+                    lead,
+                    winner,
+                });
+                round.trick = [None; 3];
+                round.state.lead = winner;
+                round.state.turn = winner;
+                // a null game is lost as soon as the declarer takes a trick
+                if round.forehand.is_empty()
+                    || (round.state.contract.map(|c| c.game) == Some(GameType::Null)
+                        && round.declarer() == Some(winner))
+                {
+                    round.state.mode = Mode::Finished;
+                }
+            }
         }
-        _ => (),
     }
 
-    return true;
+    Ok(if round.state.mode == Mode::Finished {
+        Outcome::RoundFinished
+    } else if round.state.mode != mode {
+        Outcome::PhaseChanged(round.state.mode)
+    } else if round.tricks.len() > tricks {
+        Outcome::TrickCompleted {
+            winner: round.state.lead,
+        }
+    } else {
+        Outcome::NextTurn(round.state.turn)
+    })
 }
 
-fn get_next_bidder(bids: &[u8; 3]) -> u8 {
+fn get_next_bidder(bids: &[u16; 3]) -> Option<Seat> {
     match bids {
         // f vs m
-        [f, m, 0] if f == m => return 1, // f just matched m so m turn
-        [f, m, 0] if m > f => return 0,  // m just raised
+        [f, m, 0] if f == m => Some(Seat::Middlehand), // f just matched m so m turn
+        [f, m, 0] if m > f => Some(Seat::Forehand),    // m just raised
         // f vs r
-        [f, m, r] if f == r && m <= f => return 2,
-        [f, m, r] if r > f && m <= f => return 0,
+        [f, m, r] if f == r && m <= f => Some(Seat::Rearhand),
+        [f, m, r] if r > f && m <= f => Some(Seat::Forehand),
         // m vs r
-        [f, m, r] if m == r && m > f => return 2,
-        [f, m, r] if r > m && m > f => return 1,
+        [f, m, r] if m == r && m > f => Some(Seat::Rearhand),
+        [f, m, r] if r > m && m > f => Some(Seat::Middlehand),
+
+        _ => None,
+    }
+}
+
+thread_local! {
+    // worked out once as it's needed for every bid
+    static BID_VALUES: Vec<u16> = bid_ladder();
+}
+
+// The null values and the suit and grand base values times 2 (with or
+// without one) up to every trump plus game, hand, schneider, schneider
+// announced, schwarz, schwarz announced and ouvert.
+fn bid_ladder() -> Vec<u16> {
+    let mut values = Vec::new();
+    for game in GameType::all().iter() {
+        match game {
+            GameType::Null => {
+                for hand in [false, true].iter() {
+                    for contract in Contract::all(*hand) {
+                        if contract.game == GameType::Null {
+                            values.push(contract.value(&[], false, false));
+                        }
+                    }
+                }
+            }
+            game => {
+                let max_multiplier = game.trumps().len() as u16 + 7;
+                values.extend((2..=max_multiplier).map(|m| m * game.base_value()));
+            }
+        }
+    }
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+/// Every value a game can be worth in ascending order, i.e. every legal bid.
+pub fn bid_values() -> Vec<u16> {
+    BID_VALUES.with(|values| values.clone())
+}
+
+pub fn is_legal_bid(bid: u16) -> bool {
+    BID_VALUES.with(|values| values.binary_search(&bid).is_ok())
+}
+
+fn get_next_bid(bid: &u16) -> Option<u16> {
+    BID_VALUES.with(|values| {
+        let next = match values.binary_search(bid) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        };
+        values.get(next).copied()
+    })
+}
 
-        _ => panic!(),
+/// The first available action for whoever's turn it is, None once the
+/// round is finished.
+#[cfg(test)]
+pub(crate) fn next_action(round: &Round) -> Option<(Seat, Action)> {
+    if round.state.mode == Mode::Finished {
+        return None;
     }
+    let turn = round.state.turn;
+    Some((turn, available_actions(round, turn)?.remove(0)))
 }
 
-fn get_next_bid(bid: &u8) -> u8 {
-    match bid {
-        0 => 18,
-        18 => 20,
-        20 => 22,
-        22 => 23,
-        23 => 24,
-        24 => 27,
-        27 => 30,
-        30 => 33,
-        33 => 35,
-        35 => 36,
-        36 => 40,
-        // TODO
-        _ => 1,
+/// Play the first available action until the round is finished.
+#[cfg(test)]
+pub(crate) fn play_out(round: &mut Round) {
+    while let Some((player, action)) = next_action(round) {
+        apply_action(round, action, player).unwrap();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::{parse_hand, Rank, Suit};
+    use crate::contract::GameType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_new_round() {
@@ -222,8 +1212,12 @@ mod tests {
             State {
                 bids: [0; 3],
                 modifier: 1,
-                turn: 1,
-                mode: Mode::Bidding
+                kontra: None,
+                turn: Seat::Middlehand,
+                mode: Mode::Bidding,
+                hand: None,
+                contract: None,
+                lead: Seat::Forehand,
             }
         );
         assert_eq!(round.forehand.len(), 10);
@@ -234,163 +1228,1231 @@ mod tests {
     }
 
     #[test]
-    fn test_middlehand_win_bidding() {
-        let mut round = Round {
-            state: State {
-                bids: [0; 3],
-                modifier: 1,
-                turn: 1,
-                mode: Mode::Bidding,
-            },
-            forehand: [None; 10],
-            middlehand: [None; 10],
-            rearhand: [None; 10],
-            skat: [None, None],
-            trick: [None; 3],
-        };
+    fn test_new_round_from_seed() {
+        let round = new_round_from_seed(1234);
+        assert_eq!(round.seed(), Some(1234));
+        assert_eq!(round, new_round_from_seed(1234));
+        assert_ne!(round.forehand, new_round_from_seed(1235).forehand);
 
-        assert_eq!(available_actions(&round, 2), None);
-        assert_eq!(available_actions(&round, 0), None);
+        let round = new_round();
+        assert_eq!(round, new_round_from_seed(round.seed().unwrap()));
+
+        let mut rng = StdRng::seed_from_u64(99);
+        let round = new_round_with_rng(&mut rng);
+        assert_eq!(round, new_round_with_rng(&mut StdRng::seed_from_u64(99)));
+    }
+
+    // The deck in order, forehand holds all the clubs.
+    fn builder() -> RoundBuilder {
+        let deck = Deck::new().cards;
+        RoundBuilder::new(&deck[..10], &deck[10..20], &deck[20..30], &deck[30..])
+    }
+
+    #[test]
+    fn test_middlehand_win_bidding() {
+        let mut round = builder().build().unwrap();
+
+        assert_eq!(available_actions(&round, Seat::Rearhand), None);
+        assert_eq!(available_actions(&round, Seat::Forehand), None);
 
         assert_eq!(
-            available_actions(&round, 1),
+            available_actions(&round, Seat::Middlehand),
             Some(vec![Action::Bid(18), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Bid(18), 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 0], 0));
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 0], Seat::Forehand)
+        );
 
         assert_eq!(
-            available_actions(&round, 0),
+            available_actions(&round, Seat::Forehand),
             Some(vec![Action::Bid(18), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Bid(18), 0), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 18, 0], 1));
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 18, 0], Seat::Middlehand)
+        );
 
         assert_eq!(
-            available_actions(&round, 1),
+            available_actions(&round, Seat::Middlehand),
             Some(vec![Action::Bid(20), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Bid(20), 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 20, 0], 0));
+        assert!(apply_action(&mut round, Action::Bid(20), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 20, 0], Seat::Forehand)
+        );
 
         assert_eq!(
-            available_actions(&round, 0),
+            available_actions(&round, Seat::Forehand),
             Some(vec![Action::Bid(20), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Pass, 0), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 20, 0], 2));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 20, 0], Seat::Rearhand)
+        );
 
         assert_eq!(
-            available_actions(&round, 2),
+            available_actions(&round, Seat::Rearhand),
             Some(vec![Action::Bid(22), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Bid(22), 2), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 20, 22], 1));
+        assert!(apply_action(&mut round, Action::Bid(22), Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 20, 22], Seat::Middlehand)
+        );
 
         assert_eq!(
-            available_actions(&round, 1),
+            available_actions(&round, Seat::Middlehand),
             Some(vec![Action::Bid(22), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Bid(22), 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 22, 22], 2));
+        assert!(apply_action(&mut round, Action::Bid(22), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 22, 22], Seat::Rearhand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 2), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 22, 22], 1));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 22, 22], Seat::Middlehand)
+        );
         assert_eq!(round.state.mode, Mode::Announcing);
     }
 
     #[test]
     fn test_forehand_win_bidding() {
-        let mut round = Round {
-            state: State {
-                bids: [0; 3],
-                modifier: 1,
-                turn: 1,
-                mode: Mode::Bidding,
-            },
-            forehand: [None; 10],
-            middlehand: [None; 10],
-            rearhand: [None; 10],
-            skat: [None, None],
-            trick: [None; 3],
-        };
+        let mut round = builder().build().unwrap();
 
-        assert_eq!(apply_action(&mut round, Action::Bid(18), 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 0], 0));
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 0], Seat::Forehand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Bid(18), 0), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 18, 0], 1));
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 18, 0], Seat::Middlehand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 18, 0], 2));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 18, 0], Seat::Rearhand)
+        );
 
         assert_eq!(
-            available_actions(&round, 2),
+            available_actions(&round, Seat::Rearhand),
             Some(vec![Action::Bid(20), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Pass, 2), true);
-        assert_eq!((round.state.bids, round.state.turn), ([18, 18, 0], 0));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([18, 18, 0], Seat::Forehand)
+        );
         assert_eq!(round.state.mode, Mode::Announcing);
     }
 
     #[test]
     fn test_rearhand_win_bidding() {
-        let mut round = Round {
-            state: State {
-                bids: [0; 3],
-                modifier: 1,
-                turn: 1,
-                mode: Mode::Bidding,
-            },
-            forehand: [None; 10],
-            middlehand: [None; 10],
-            rearhand: [None; 10],
-            skat: [None, None],
-            trick: [None; 3],
-        };
+        let mut round = builder().build().unwrap();
 
-        assert_eq!(apply_action(&mut round, Action::Bid(18), 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 0], 0));
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 0], Seat::Forehand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 0), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 0], 2));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 0], Seat::Rearhand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Bid(20), 2), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 20], 1));
+        assert!(apply_action(&mut round, Action::Bid(20), Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 20], Seat::Middlehand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 18, 20], 2));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 18, 20], Seat::Rearhand)
+        );
         assert_eq!(round.state.mode, Mode::Announcing);
     }
 
     #[test]
     fn test_all_pass_in_bidding() {
-        let mut round = Round {
-            state: State {
-                bids: [0; 3],
-                modifier: 1,
-                turn: 1,
-                mode: Mode::Bidding,
-            },
-            forehand: [None; 10],
-            middlehand: [None; 10],
-            rearhand: [None; 10],
-            skat: [None, None],
-            trick: [None; 3],
-        };
+        let mut round = builder().build().unwrap();
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 1), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 0, 0], 2));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 0, 0], Seat::Rearhand)
+        );
 
-        assert_eq!(apply_action(&mut round, Action::Pass, 2), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 0, 0], 0));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 0, 0], Seat::Forehand)
+        );
 
         assert_eq!(
-            available_actions(&round, 0),
+            available_actions(&round, Seat::Forehand),
             Some(vec![Action::Bid(18), Action::Pass])
         );
-        assert_eq!(apply_action(&mut round, Action::Pass, 0), true);
-        assert_eq!((round.state.bids, round.state.turn), ([0, 0, 0], 0));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([0, 0, 0], Seat::Forehand)
+        );
         assert_eq!(round.state.mode, Mode::Finished);
     }
+
+    #[test]
+    fn test_bid_values() {
+        let values = bid_values();
+        assert_eq!(values.len(), 63);
+        assert_eq!(
+            values[..16],
+            [18, 20, 22, 23, 24, 27, 30, 33, 35, 36, 40, 44, 45, 46, 48, 50]
+        );
+        assert_eq!(values.last(), Some(&264));
+
+        assert!(is_legal_bid(59));
+        assert!(is_legal_bid(216));
+        assert!(!is_legal_bid(19));
+        assert!(!is_legal_bid(265));
+
+        assert_eq!(get_next_bid(&0), Some(18));
+        assert_eq!(get_next_bid(&40), Some(44));
+        assert_eq!(get_next_bid(&240), Some(264));
+        assert_eq!(get_next_bid(&264), None);
+    }
+
+    #[test]
+    fn test_bidding_past_forty() {
+        let mut round = builder().bids([40, 40, 0]).build().unwrap();
+
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand),
+            Some(vec![Action::Bid(44), Action::Pass])
+        );
+        assert!(apply_action(&mut round, Action::Bid(44), Seat::Middlehand).is_ok());
+        assert_eq!(
+            (round.state.bids, round.state.turn),
+            ([40, 44, 0], Seat::Forehand)
+        );
+
+        round.state.bids = [264, 264, 0];
+        round.state.turn = Seat::Middlehand;
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand),
+            Some(vec![Action::Pass])
+        );
+    }
+
+    #[test]
+    fn test_take_skat() {
+        let mut round = new_round();
+        round.state.bids = [18, 0, 0];
+        round.state.turn = Seat::Forehand;
+        round.state.mode = Mode::Announcing;
+        let hand = round.hand(Seat::Forehand);
+        let skat: Vec<Card> = round.skat.iter().collect();
+
+        assert_eq!(
+            available_actions(&round, Seat::Forehand),
+            Some(vec![Action::TakeSkat, Action::PlayHand])
+        );
+        assert!(apply_action(&mut round, Action::TakeSkat, Seat::Forehand).is_ok());
+        assert_eq!(round.state.mode, Mode::Discarding);
+        assert_eq!(round.hand(Seat::Forehand).len(), 12);
+        assert!(round.skat.is_empty());
+        assert_eq!(round.validate(), Ok(()));
+        assert_eq!(available_actions(&round, Seat::Forehand).unwrap().len(), 66);
+
+        // only cards from the declarer's own 12 can be discarded
+        let other = round.middlehand.iter().next().unwrap();
+        assert_eq!(
+            apply_action(
+                &mut round,
+                Action::ReturnSkat([hand[0], other]),
+                Seat::Forehand
+            ),
+            Err(ActionError::CardNotInHand(other))
+        );
+        assert_eq!(
+            apply_action(
+                &mut round,
+                Action::ReturnSkat([hand[0], hand[0]]),
+                Seat::Forehand
+            ),
+            Err(ActionError::DuplicateCard(hand[0]))
+        );
+
+        assert!(apply_action(
+            &mut round,
+            Action::ReturnSkat([skat[0], hand[3]]),
+            Seat::Forehand
+        )
+        .is_ok());
+        assert_eq!(round.state.mode, Mode::Announcing);
+        assert_eq!(round.skat, [skat[0], hand[3]].iter().collect());
+        let new_hand = round.hand(Seat::Forehand);
+        assert_eq!(new_hand.len(), 10);
+        assert!(new_hand.contains(&skat[1]));
+        assert!(!new_hand.contains(&hand[3]));
+        assert_eq!(
+            available_actions(&round, Seat::Forehand),
+            Some(
+                Contract::all(false)
+                    .into_iter()
+                    .map(Action::Announce)
+                    .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn test_play_hand() {
+        let mut round = new_round();
+        round.state.bids = [0, 18, 20];
+        round.state.turn = Seat::Rearhand;
+        round.state.mode = Mode::Announcing;
+        let skat = round.skat;
+
+        assert_eq!(round.declarer(), Some(Seat::Rearhand));
+        assert!(apply_action(&mut round, Action::PlayHand, Seat::Rearhand).is_ok());
+        assert_eq!(round.state.mode, Mode::Announcing);
+        assert_eq!(round.skat, skat);
+        assert_eq!(round.hand(Seat::Rearhand).len(), 10);
+
+        // announcing requires the contract to match the skat decision
+        let grand = Contract::new(GameType::Grand);
+        assert_eq!(
+            apply_action(&mut round, Action::Announce(grand), Seat::Rearhand),
+            Err(ActionError::IllegalContract(grand))
+        );
+        let grand_hand = Contract {
+            hand: true,
+            schneider: true,
+            ..grand
+        };
+        assert!(apply_action(&mut round, Action::Announce(grand_hand), Seat::Rearhand).is_ok());
+        assert_eq!(
+            (round.state.mode, round.state.turn),
+            (Mode::Playing, Seat::Forehand)
+        );
+        assert_eq!(round.state.contract, Some(grand_hand));
+    }
+
+    fn card(suit: Suit, rank: Rank) -> Option<Card> {
+        Some(Card { suit, rank })
+    }
+
+    fn playing_round(game: GameType) -> Round {
+        let mut round = new_round();
+        round.state.bids = [18, 0, 0];
+        round.state.hand = Some(false);
+        round.state.contract = Some(Contract::new(game));
+        round.state.mode = Mode::Playing;
+        round.state.turn = Seat::Forehand;
+        round.forehand = set("H7 CA");
+        round.middlehand = set("CJ ST");
+        round.rearhand = set("C7 HA");
+        round
+    }
+
+    fn set(cards: &str) -> CardSet {
+        parse_hand(cards).unwrap().into_iter().collect()
+    }
+
+    fn play(card: Option<Card>) -> Action {
+        Action::Cards(vec![card.unwrap()])
+    }
+
+    #[test]
+    fn test_follow_suit_game() {
+        let mut round = playing_round(GameType::Suit(Suit::Heart));
+        let heart_seven = card(Suit::Heart, Rank::Seven);
+
+        assert_eq!(available_actions(&round, Seat::Middlehand), None);
+        assert_eq!(
+            available_actions(&round, Seat::Forehand),
+            Some(vec![play(card(Suit::Club, Rank::Ace)), play(heart_seven)])
+        );
+        assert!(apply_action(&mut round, play(heart_seven), Seat::Forehand).is_ok());
+        assert_eq!(
+            round.hand(Seat::Forehand),
+            vec![card(Suit::Club, Rank::Ace).unwrap()]
+        );
+        assert_eq!(round.trick, [heart_seven, None, None]);
+        assert_eq!(round.state.turn, Seat::Middlehand);
+
+        // the jack is a trump so must be played on a trump lead
+        let jack = card(Suit::Club, Rank::Jack);
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand),
+            Some(vec![play(jack)])
+        );
+        assert_eq!(
+            apply_action(
+                &mut round,
+                play(card(Suit::Spade, Rank::Ten)),
+                Seat::Middlehand
+            ),
+            Err(ActionError::MustFollowSuit(
+                card(Suit::Spade, Rank::Ten).unwrap()
+            ))
+        );
+        assert!(apply_action(&mut round, play(jack), Seat::Middlehand).is_ok());
+
+        let heart_ace = card(Suit::Heart, Rank::Ace);
+        assert_eq!(
+            available_actions(&round, Seat::Rearhand),
+            Some(vec![play(heart_ace)])
+        );
+        assert!(apply_action(&mut round, play(heart_ace), Seat::Rearhand).is_ok());
+        assert_eq!(round.trick, [None; 3]);
+        assert_eq!(
+            round.hand(Seat::Rearhand),
+            vec![card(Suit::Club, Rank::Seven).unwrap()]
+        );
+        assert_eq!(round.state.mode, Mode::Playing);
+
+        // the jack won so middlehand leads
+        assert_eq!(
+            (round.state.lead, round.state.turn),
+            (Seat::Middlehand, Seat::Middlehand)
+        );
+        assert_eq!(
+            round.tricks,
+            vec![Trick {
+                cards: [heart_seven.unwrap(), jack.unwrap(), heart_ace.unwrap()],
+                lead: Seat::Forehand,
+                winner: Seat::Middlehand,
+            }]
+        );
+        assert_eq!(round.declarer_tricks().len(), 0);
+        assert_eq!(round.defender_tricks().len(), 1);
+
+        let spade_ten = card(Suit::Spade, Rank::Ten);
+        assert!(apply_action(&mut round, play(spade_ten), Seat::Middlehand).is_ok());
+        assert_eq!(round.state.turn, Seat::Rearhand);
+        let club_seven = card(Suit::Club, Rank::Seven);
+        assert!(apply_action(&mut round, play(club_seven), Seat::Rearhand).is_ok());
+        let club_ace = card(Suit::Club, Rank::Ace);
+        assert!(apply_action(&mut round, play(club_ace), Seat::Forehand).is_ok());
+        assert_eq!(round.tricks[1].winner, Seat::Middlehand);
+        assert_eq!(round.state.mode, Mode::Finished);
+    }
+
+    #[test]
+    fn test_follow_suit_grand_and_null() {
+        let club_ace = card(Suit::Club, Rank::Ace);
+
+        // in grand the jack isn't a club so middlehand can't follow
+        let mut round = playing_round(GameType::Grand);
+        assert!(apply_action(&mut round, play(club_ace), Seat::Forehand).is_ok());
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand).unwrap().len(),
+            2
+        );
+
+        // in null the jack is just another club
+        let mut round = playing_round(GameType::Null);
+        assert!(apply_action(&mut round, play(club_ace), Seat::Forehand).is_ok());
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand),
+            Some(vec![play(card(Suit::Club, Rank::Jack))])
+        );
+
+        // nor does a jack have to be played on a heart lead
+        let mut round = playing_round(GameType::Null);
+        let heart_seven = card(Suit::Heart, Rank::Seven);
+        assert!(apply_action(&mut round, play(heart_seven), Seat::Forehand).is_ok());
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_play_to_the_end() {
+        let mut round = playing_round(GameType::Null);
+        while round.state.mode == Mode::Playing {
+            let turn = round.state.turn;
+            let action = available_actions(&round, turn).unwrap().pop().unwrap();
+            assert!(apply_action(&mut round, action, turn).is_ok());
+        }
+        assert_eq!(round.state.mode, Mode::Finished);
+        for player in Seat::all().iter().copied() {
+            assert_eq!(round.hand(player), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut round = builder().build().unwrap();
+        assert!(apply_action(&mut round, Action::Bid(18), Seat::Middlehand).is_ok());
+        assert!(apply_action(&mut round, Action::Bid(20), Seat::Middlehand).is_err());
+        let mut views = vec![round.view_for(Seat::Middlehand)];
+        while round.state.mode != Mode::Finished {
+            let turn = round.state.turn;
+            let action = available_actions(&round, turn).unwrap().pop().unwrap();
+            assert!(apply_action(&mut round, action, turn).is_ok());
+            views.push(round.view_for(Seat::Middlehand));
+        }
+
+        // only what was applied is logged
+        let events = round.events().to_vec();
+        assert_eq!(events.len(), views.len());
+        assert_eq!(
+            events[0],
+            Event {
+                seat: Seat::Middlehand,
+                action: Action::Bid(18),
+            }
+        );
+        let deal = round.deal().clone();
+        assert_eq!(Round::replay(&deal, &events), Ok(round));
+        for (n, view) in views.into_iter().enumerate() {
+            let replayed = Round::replay(&deal, &events[..=n]).unwrap();
+            assert_eq!(replayed.view_for(Seat::Middlehand), view);
+        }
+
+        let mut bad = events;
+        bad.swap(0, 1);
+        assert_eq!(
+            Round::replay(&deal, &bad).map(|_| ()),
+            Err(RoundError::IllegalEvent(0))
+        );
+    }
+
+    #[test]
+    fn test_replay_builder() {
+        let deal = new_round_from_seed(7);
+        let hands: Vec<Vec<Card>> = Seat::all()
+            .iter()
+            .map(|player| deal.hand(*player))
+            .collect();
+        let skat: Vec<Card> = deal.skat.iter().collect();
+        let setup = || {
+            RoundBuilder::new(&hands[0], &hands[1], &hands[2], &skat)
+                .bids([18, 0, 0])
+                .contract(Contract::new(GameType::Grand))
+        };
+        let mut played = setup().build().unwrap();
+        for _ in 0..6 {
+            let (player, action) = next_action(&played).unwrap();
+            apply_action(&mut played, action, player).unwrap();
+        }
+        // forehand doesn't lead the second trick
+        assert_eq!(played.tricks[1].lead, Seat::Rearhand);
+        let tricks: Vec<[Card; 3]> = played.tricks.iter().map(Trick::play_order).collect();
+
+        let mut round = setup().tricks(&tricks).build().unwrap();
+        assert_eq!(round.deal().tricks.len(), 2);
+        let mut views = vec![round.view_for(Seat::Forehand)];
+        while let Some((player, action)) = next_action(&round) {
+            apply_action(&mut round, action, player).unwrap();
+            views.push(round.view_for(Seat::Forehand));
+        }
+        assert_eq!(round.events().len(), 24);
+
+        let deal = round.deal().clone();
+        let events = round.events().to_vec();
+        for (n, view) in views.into_iter().enumerate() {
+            let replayed = Round::replay(&deal, &events[..n]).unwrap();
+            assert_eq!(replayed.view_for(Seat::Forehand), view);
+        }
+        assert_eq!(Round::replay(&deal, &events), Ok(round));
+    }
+
+    #[test]
+    fn test_rules_fixed_once_played() {
+        let mut round = new_round();
+        let ramsch = Rules {
+            ramsch: true,
+            ..Rules::default()
+        };
+        for player in [Seat::Middlehand, Seat::Rearhand].iter() {
+            assert!(apply_action(&mut round, Action::Pass, *player).is_ok());
+        }
+        assert!(!round.set_rules(ramsch));
+        assert!(apply_action(&mut round, Action::Pass, Seat::Forehand).is_ok());
+        assert_eq!(round.mode(), Mode::Finished);
+        assert_eq!(
+            Round::replay(round.deal(), round.events()).map(|round| round.mode()),
+            Ok(Mode::Finished)
+        );
+
+        let mut round = new_round();
+        assert!(round.set_rules(ramsch));
+        for player in [Seat::Middlehand, Seat::Rearhand, Seat::Forehand].iter() {
+            assert!(apply_action(&mut round, Action::Pass, *player).is_ok());
+        }
+        assert_eq!(
+            Round::replay(round.deal(), round.events()).map(|round| round.mode()),
+            Ok(Mode::Ramsch)
+        );
+    }
+
+    #[test]
+    fn test_replay_ramsch() {
+        let mut round = new_round_from_seed(7);
+        assert!(round.play_ramsch());
+        let turn = round.state.turn;
+        let action = available_actions(&round, turn).unwrap().remove(0);
+        assert!(apply_action(&mut round, action, turn).is_ok());
+
+        let replayed = Round::replay(round.deal(), round.events()).unwrap();
+        assert_eq!(replayed.seed(), Some(7));
+        assert_eq!(replayed, round);
+    }
+
+    #[test]
+    fn test_card_points() {
+        let round = new_round();
+        let total: u8 = Seat::all()
+            .iter()
+            .flat_map(|player| round.hand(*player))
+            .chain(round.skat.iter())
+            .map(|card| card.points())
+            .sum();
+        assert_eq!(total, 120);
+    }
+
+    #[test]
+    fn test_game_result() {
+        let contract = Contract::new(GameType::Suit(Suit::Club));
+        let result = |points, tricks| {
+            GameResult::new(
+                Seat::Forehand,
+                contract,
+                18,
+                &[],
+                points,
+                tricks,
+                10 - tricks,
+            )
+        };
+
+        assert!(result(61, 5).won);
+        assert!(!result(60, 5).won);
+        assert!(!result(89, 8).schneider);
+        assert!(result(90, 8).schneider);
+        assert!(!result(31, 2).schneider);
+        let lost = result(30, 2);
+        assert_eq!((lost.won, lost.schneider), (false, true));
+        assert!(result(120, 10).schwarz);
+        assert!(result(0, 0).schwarz);
+        assert!(!result(120, 9).schwarz);
+
+        // announcements raise the bar
+        let contract = Contract {
+            hand: true,
+            schneider: true,
+            ..contract
+        };
+        assert!(!GameResult::new(Seat::Forehand, contract, 18, &[], 89, 8, 2).won);
+        assert!(GameResult::new(Seat::Forehand, contract, 18, &[], 90, 8, 2).won);
+        let contract = Contract {
+            schwarz: true,
+            ..contract
+        };
+        assert!(!GameResult::new(Seat::Forehand, contract, 18, &[], 120, 9, 1).won);
+        assert!(GameResult::new(Seat::Forehand, contract, 18, &[], 120, 10, 0).won);
+
+        let null = Contract::new(GameType::Null);
+        assert!(GameResult::new(Seat::Forehand, null, 18, &[], 0, 0, 10).won);
+        assert!(!GameResult::new(Seat::Forehand, null, 18, &[], 0, 1, 0).won);
+    }
+
+    #[test]
+    fn test_null_lost_on_first_trick() {
+        let mut round = playing_round(GameType::Null);
+        let club_ace = card(Suit::Club, Rank::Ace);
+        let club_jack = card(Suit::Club, Rank::Jack);
+        let club_seven = card(Suit::Club, Rank::Seven);
+        assert!(apply_action(&mut round, play(club_ace), Seat::Forehand).is_ok());
+        assert!(apply_action(&mut round, play(club_jack), Seat::Middlehand).is_ok());
+        assert_eq!(round.result(), None);
+        assert!(apply_action(&mut round, play(club_seven), Seat::Rearhand).is_ok());
+
+        assert_eq!(round.state.mode, Mode::Finished);
+        let result = round.result().unwrap();
+        assert_eq!((result.declarer, result.won), (Seat::Forehand, false));
+    }
+
+    #[test]
+    fn test_result_counts_the_skat() {
+        let mut round = playing_round(GameType::Grand);
+        round.skat = set("SA DT");
+        play_out(&mut round);
+
+        // middlehand trumps the heart seven and then wins with the spade ten
+        let result = round.result().unwrap();
+        assert_eq!(result.points, 11 + 10);
+        // without 4, game 5, schneider 6, schwarz 7
+        assert_eq!(result.value, 7 * 24);
+        assert_eq!(
+            (result.won, result.schneider, result.schwarz),
+            (false, true, true)
+        );
+    }
+
+    #[test]
+    fn test_overbid() {
+        let cards = [Card {
+            suit: Suit::Club,
+            rank: Rank::Jack,
+        }];
+        let clubs = Contract {
+            hand: true,
+            ..Contract::new(GameType::Suit(Suit::Club))
+        };
+
+        // with 1, game 2, hand 3
+        let result = GameResult::new(Seat::Forehand, clubs, 36, &cards, 70, 6, 4);
+        assert_eq!(
+            (result.won, result.overbid, result.value),
+            (true, false, 36)
+        );
+
+        // lost at the next multiple of 12 above the bid
+        let result = GameResult::new(Seat::Forehand, clubs, 40, &cards, 70, 6, 4);
+        assert_eq!(
+            (result.won, result.overbid, result.value),
+            (false, true, 48)
+        );
+
+        // schneider makes the game worth 48
+        let result = GameResult::new(Seat::Forehand, clubs, 48, &cards, 95, 8, 2);
+        assert_eq!(
+            (result.won, result.overbid, result.value),
+            (true, false, 48)
+        );
+        let result = GameResult::new(Seat::Forehand, clubs, 48, &cards, 89, 8, 2);
+        assert_eq!(
+            (result.won, result.overbid, result.value),
+            (false, true, 48)
+        );
+    }
+
+    #[test]
+    fn test_null_not_offered_above_value() {
+        let mut round = new_round();
+        round.state.bids = [0, 24, 0];
+        round.state.turn = Seat::Middlehand;
+        round.state.mode = Mode::Announcing;
+        round.state.hand = Some(false);
+
+        let null = Contract::new(GameType::Null);
+        let null_ouvert = Contract {
+            ouvert: true,
+            ..null
+        };
+        let actions = available_actions(&round, Seat::Middlehand).unwrap();
+        assert!(!actions.contains(&Action::Announce(null)));
+        assert!(actions.contains(&Action::Announce(null_ouvert)));
+    }
+
+    #[test]
+    fn test_all_pass_into_ramsch() {
+        let mut round = new_round();
+        round.set_rules(Rules {
+            ramsch: true,
+            ..Rules::default()
+        });
+
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert!(apply_action(&mut round, Action::Pass, Seat::Rearhand).is_ok());
+        assert!(apply_action(&mut round, Action::Pass, Seat::Forehand).is_ok());
+        assert_eq!(
+            (round.state.mode, round.state.turn),
+            (Mode::Ramsch, Seat::Forehand)
+        );
+
+        play_out(&mut round);
+        assert_eq!(round.result(), None);
+        let result = round.ramsch_result().unwrap();
+        assert_eq!(result.points.iter().sum::<u8>(), 120);
+        assert!(!result.losers.is_empty());
+    }
+
+    #[test]
+    fn test_play_ramsch() {
+        let mut round = new_round();
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert!(!round.play_ramsch());
+
+        let mut round = new_round();
+        assert!(round.play_ramsch());
+        assert_eq!(
+            (round.mode(), round.state.turn),
+            (Mode::Ramsch, Seat::Forehand)
+        );
+        assert_eq!(available_actions(&round, Seat::Forehand).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_ramsch_result() {
+        let result = RamschResult::new([50, 40, 30], [4, 3, 3]);
+        assert_eq!(result.losers, vec![Seat::Forehand]);
+        assert_eq!((result.jungfrau, result.durchmarsch), (0, None));
+
+        let result = RamschResult::new([60, 60, 0], [5, 5, 0]);
+        assert_eq!(result.losers, vec![Seat::Forehand, Seat::Middlehand]);
+        assert_eq!((result.jungfrau, result.durchmarsch), (1, None));
+
+        let result = RamschResult::new([0, 120, 0], [0, 10, 0]);
+        assert_eq!(result.losers, vec![Seat::Middlehand]);
+        assert_eq!(
+            (result.jungfrau, result.durchmarsch),
+            (2, Some(Seat::Middlehand))
+        );
+    }
+
+    #[test]
+    fn test_kontra_re() {
+        let setup = || {
+            builder()
+                .bids([18, 0, 0])
+                .contract(Contract::new(GameType::Suit(Suit::Heart)))
+        };
+        let round = setup().build().unwrap();
+        assert_eq!(available_actions(&round, Seat::Middlehand), None);
+
+        let mut round = setup()
+            .rules(Rules {
+                kontra: true,
+                ..Rules::default()
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            available_actions(&round, Seat::Middlehand),
+            Some(vec![Action::Kontra])
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::Re, Seat::Forehand),
+            Err(ActionError::CannotDouble)
+        );
+
+        // play the first card rather than doubling
+        let play_card = |round: &mut Round| {
+            let turn = round.state.turn;
+            let action = available_actions(round, turn)
+                .unwrap()
+                .into_iter()
+                .find(|action| matches!(action, Action::Cards(_)))
+                .unwrap();
+            assert!(apply_action(round, action, turn).is_ok());
+        };
+
+        // middlehand has played so only rearhand can still double
+        play_card(&mut round);
+        play_card(&mut round);
+        assert_eq!(available_actions(&round, Seat::Middlehand), None);
+        assert_eq!(
+            available_actions(&round, Seat::Rearhand).unwrap()[0],
+            Action::Kontra
+        );
+        assert!(apply_action(&mut round, Action::Kontra, Seat::Rearhand).is_ok());
+        assert_eq!(round.state.modifier, 2);
+        assert_eq!(
+            available_actions(&round, Seat::Forehand),
+            Some(vec![Action::Re])
+        );
+
+        // re is offered alongside the declarer's cards until they play one
+        while round.state.turn != Seat::Forehand {
+            play_card(&mut round);
+        }
+        let mut actions = available_actions(&round, Seat::Forehand).unwrap();
+        assert_eq!(actions[0], Action::Re);
+        assert!(apply_action(&mut round, actions.remove(1), Seat::Forehand).is_ok());
+        assert_eq!(
+            apply_action(&mut round, Action::Re, Seat::Forehand),
+            Err(ActionError::CannotDouble)
+        );
+        assert_eq!(round.state.modifier, 2);
+        assert_eq!(round.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_kontra_only_before_first_card() {
+        let mut round = playing_round(GameType::Grand);
+        round.set_rules(Rules {
+            kontra: true,
+            ..Rules::default()
+        });
+        round.middlehand = new_round().middlehand;
+
+        let action = available_actions(&round, Seat::Forehand).unwrap().remove(0);
+        assert!(apply_action(&mut round, action, Seat::Forehand).is_ok());
+        let mut actions = available_actions(&round, Seat::Middlehand).unwrap();
+        assert_eq!(actions[0], Action::Kontra);
+        assert!(apply_action(&mut round, actions.pop().unwrap(), Seat::Middlehand).is_ok());
+        assert_eq!(available_actions(&round, Seat::Middlehand), None);
+        assert_eq!(
+            apply_action(&mut round, Action::Kontra, Seat::Middlehand),
+            Err(ActionError::CannotDouble)
+        );
+    }
+
+    #[test]
+    fn test_kontra_doubles_result() {
+        let mut round = playing_round(GameType::Grand);
+        round.state.modifier = 2;
+        round.state.kontra = Some(2);
+        play_out(&mut round);
+        assert_eq!(round.result().unwrap().modifier, 2);
+    }
+
+    #[test]
+    fn test_action_errors() {
+        let mut round = new_round();
+        assert_eq!(
+            apply_action(&mut round, Action::Bid(18), Seat::Forehand),
+            Err(ActionError::NotYourTurn)
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::Bid(19), Seat::Middlehand),
+            Err(ActionError::IllegalBid(19))
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::TakeSkat, Seat::Middlehand),
+            Err(ActionError::WrongPhase(Mode::Bidding))
+        );
+        assert_eq!(
+            ActionError::WrongPhase(Mode::Bidding).to_string(),
+            "That can't be done in the bidding phase."
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::Kontra, Seat::Middlehand),
+            Err(ActionError::CannotDouble)
+        );
+
+        round.state.bids = [0, 18, 0];
+        round.state.mode = Mode::Announcing;
+        let grand = Contract::new(GameType::Grand);
+        assert_eq!(
+            apply_action(&mut round, Action::Announce(grand), Seat::Middlehand),
+            Err(ActionError::SkatNotDecided)
+        );
+        assert!(apply_action(&mut round, Action::PlayHand, Seat::Middlehand).is_ok());
+        assert_eq!(
+            apply_action(&mut round, Action::TakeSkat, Seat::Middlehand),
+            Err(ActionError::SkatAlreadyDecided)
+        );
+
+        let mut round = playing_round(GameType::Grand);
+        let cards = round.hand(Seat::Forehand);
+        assert_eq!(
+            apply_action(&mut round, Action::Cards(cards), Seat::Forehand),
+            Err(ActionError::WrongCardCount(2))
+        );
+        let other = round.hand(Seat::Middlehand)[0];
+        assert_eq!(
+            apply_action(&mut round, Action::Cards(vec![other]), Seat::Forehand),
+            Err(ActionError::CardNotInHand(other))
+        );
+    }
+
+    #[test]
+    fn test_outcomes() {
+        let mut round = new_round();
+        assert_eq!(
+            apply_action(&mut round, Action::Bid(18), Seat::Middlehand),
+            Ok(Outcome::NextTurn(Seat::Forehand))
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::Pass, Seat::Forehand),
+            Ok(Outcome::NextTurn(Seat::Rearhand))
+        );
+        assert_eq!(
+            apply_action(&mut round, Action::Pass, Seat::Rearhand),
+            Ok(Outcome::PhaseChanged(Mode::Announcing))
+        );
+
+        let mut round = playing_round(GameType::Suit(Suit::Heart));
+        assert_eq!(
+            apply_action(
+                &mut round,
+                play(card(Suit::Heart, Rank::Seven)),
+                Seat::Forehand
+            ),
+            Ok(Outcome::NextTurn(Seat::Middlehand))
+        );
+        assert!(apply_action(
+            &mut round,
+            play(card(Suit::Club, Rank::Jack)),
+            Seat::Middlehand
+        )
+        .is_ok());
+        assert_eq!(
+            apply_action(
+                &mut round,
+                play(card(Suit::Heart, Rank::Ace)),
+                Seat::Rearhand
+            ),
+            Ok(Outcome::TrickCompleted {
+                winner: Seat::Middlehand
+            })
+        );
+        assert!(apply_action(
+            &mut round,
+            play(card(Suit::Spade, Rank::Ten)),
+            Seat::Middlehand
+        )
+        .is_ok());
+        assert!(apply_action(
+            &mut round,
+            play(card(Suit::Club, Rank::Seven)),
+            Seat::Rearhand
+        )
+        .is_ok());
+        assert_eq!(
+            apply_action(
+                &mut round,
+                play(card(Suit::Club, Rank::Ace)),
+                Seat::Forehand
+            ),
+            Ok(Outcome::RoundFinished)
+        );
+    }
+
+    #[test]
+    fn test_forehand_bids_after_passes() {
+        let mut round = new_round();
+        assert!(apply_action(&mut round, Action::Pass, Seat::Middlehand).is_ok());
+        assert!(apply_action(&mut round, Action::Pass, Seat::Rearhand).is_ok());
+        assert_eq!(
+            apply_action(&mut round, Action::Bid(18), Seat::Forehand),
+            Ok(Outcome::PhaseChanged(Mode::Announcing))
+        );
+        assert_eq!(
+            (round.declarer(), round.state.turn),
+            (Some(Seat::Forehand), Seat::Forehand)
+        );
+    }
+
+    #[test]
+    fn test_malformed_round() {
+        let mut round = new_round();
+        round.state.bids = [20, 18, 0];
+        round.state.turn = Seat::Forehand;
+        assert_eq!(round.validate(), Err(RoundError::BidsOutOfOrder));
+        assert_eq!(
+            apply_action(&mut round, Action::Bid(22), Seat::Forehand),
+            Err(ActionError::InvalidRound(RoundError::BidsOutOfOrder))
+        );
+        assert_eq!(round.state.bids, [20, 18, 0]);
+
+        let mut round = new_round();
+        round.state.mode = Mode::Playing;
+        assert_eq!(
+            apply_action(&mut round, Action::Pass, Seat::Middlehand),
+            Err(ActionError::InvalidRound(RoundError::NoDeclarer))
+        );
+        round.state.bids = [0, 18, 0];
+        assert_eq!(round.validate(), Err(RoundError::NoContract));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut round = new_round();
+        assert_eq!(round.validate(), Ok(()));
+
+        let card = round.forehand.iter().next().unwrap();
+        let own = round.middlehand.iter().next().unwrap();
+        round.middlehand.remove(own);
+        round.middlehand.insert(card);
+        assert_eq!(round.validate(), Err(RoundError::DuplicateCard(card)));
+
+        let mut round = new_round();
+        let card = round.skat.iter().next().unwrap();
+        round.skat.remove(card);
+        assert_eq!(round.validate(), Err(RoundError::MissingCards(1)));
+
+        let mut round = new_round();
+        let card = round.rearhand.iter().next().unwrap();
+        round.rearhand.remove(card);
+        assert_eq!(
+            round.validate(),
+            Err(RoundError::WrongHandSize(Seat::Rearhand))
+        );
+
+        let mut round = new_round();
+        round.state.bids = [0, 19, 0];
+        assert_eq!(round.validate(), Err(RoundError::IllegalBid(19)));
+
+        // a round played to the end is still consistent
+        let mut round = new_round();
+        round.set_rules(Rules {
+            ramsch: true,
+            ..Rules::default()
+        });
+        for player in [Seat::Middlehand, Seat::Rearhand, Seat::Forehand].iter() {
+            assert!(apply_action(&mut round, Action::Pass, *player).is_ok());
+        }
+        while round.state.mode == Mode::Ramsch {
+            let turn = round.state.turn;
+            let action = available_actions(&round, turn).unwrap().remove(0);
+            assert!(apply_action(&mut round, action, turn).is_ok());
+            assert_eq!(round.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_view_for() {
+        let mut round = new_round();
+        let view = round.view_for(Seat::Rearhand);
+        assert_eq!(view.hand, round.hand(Seat::Rearhand));
+        assert_eq!(view.hand_sizes, [10, 10, 10]);
+        assert_eq!((view.skat_size, view.skat), (2, None));
+        assert_eq!(view.actions, vec![]);
+        assert_eq!(
+            round.view_for(Seat::Middlehand).actions,
+            vec![Action::Bid(18), Action::Pass]
+        );
+
+        round.state.bids = [0, 18, 0];
+        round.state.turn = Seat::Middlehand;
+        round.state.mode = Mode::Announcing;
+        assert!(apply_action(&mut round, Action::TakeSkat, Seat::Middlehand).is_ok());
+        let view = round.view_for(Seat::Middlehand);
+        assert_eq!(view.hand_sizes, [10, 12, 10]);
+        assert_eq!(view.skat_size, 0);
+        assert_eq!(round.view_for(Seat::Forehand).skat, None);
+
+        let discards = [view.hand[0], view.hand[1]];
+        assert!(apply_action(&mut round, Action::ReturnSkat(discards), Seat::Middlehand).is_ok());
+        assert_eq!(
+            round.view_for(Seat::Middlehand).skat,
+            Some(discards.to_vec())
+        );
+        assert_eq!(round.view_for(Seat::Rearhand).skat, None);
+        assert_eq!(round.view_for(Seat::Rearhand).skat_size, 2);
+        assert_eq!(round.view_for(Seat::Rearhand).open_hand, None);
+    }
+
+    #[test]
+    fn test_view_for_ouvert() {
+        let mut round = playing_round(GameType::Null);
+        round.state.contract = Some(Contract {
+            ouvert: true,
+            ..Contract::new(GameType::Null)
+        });
+        let view = round.view_for(Seat::Middlehand);
+        assert_eq!(view.open_hand, Some(round.hand(Seat::Forehand)));
+        assert_eq!(view.hand, round.hand(Seat::Middlehand));
+        assert_eq!(view.hand_sizes, [2, 2, 2]);
+    }
+
+    #[test]
+    fn test_round_builder_validates_deal() {
+        let deck = Deck::new().cards;
+        assert!(builder().build().unwrap().validate().is_ok());
+        assert_eq!(
+            RoundBuilder::new(&deck[..10], &deck[..10], &deck[20..30], &deck[30..]).build(),
+            Err(RoundError::DuplicateCard(deck[0]))
+        );
+        assert_eq!(
+            RoundBuilder::new(&deck[..9], &deck[9..20], &deck[20..30], &deck[30..]).build(),
+            Err(RoundError::WrongHandSize(Seat::Forehand))
+        );
+        assert_eq!(
+            RoundBuilder::new(&deck[..10], &deck[10..20], &deck[20..30], &deck[29..]).build(),
+            Err(RoundError::WrongSkatSize(3))
+        );
+        assert_eq!(
+            RoundBuilder::new(&deck[..10], &deck[10..20], &deck[20..30], &deck[30..31]).build(),
+            Err(RoundError::MissingCards(1))
+        );
+
+        let contract = Contract {
+            schneider: true,
+            ..Contract::new(GameType::Null)
+        };
+        assert_eq!(
+            builder().bids([18, 0, 0]).contract(contract).build(),
+            Err(RoundError::IllegalContract(contract))
+        );
+        assert_eq!(
+            builder().contract(Contract::new(GameType::Grand)).build(),
+            Err(RoundError::NoDeclarer)
+        );
+
+        let round = builder()
+            .bids([0, 18, 0])
+            .mode(Mode::Announcing)
+            .build()
+            .unwrap();
+        assert_eq!(round.state.turn, Seat::Middlehand);
+    }
+
+    #[test]
+    fn test_round_builder_tricks() {
+        let deal = new_round_from_seed(7);
+        let hands: Vec<Vec<Card>> = Seat::all()
+            .iter()
+            .map(|player| deal.hand(*player))
+            .collect();
+        let skat: Vec<Card> = deal.skat.iter().collect();
+        let setup = || {
+            RoundBuilder::new(&hands[0], &hands[1], &hands[2], &skat)
+                .bids([18, 0, 0])
+                .contract(Contract::new(GameType::Grand))
+        };
+
+        let mut round = setup().build().unwrap();
+        assert_eq!(
+            (round.mode(), round.state.turn),
+            (Mode::Playing, Seat::Forehand)
+        );
+        let mut played = Vec::new();
+        for _ in 0..4 {
+            let mut trick = Vec::new();
+            for _ in 0..3 {
+                let player = round.state.turn;
+                let action = available_actions(&round, player).unwrap().remove(0);
+                if let Action::Cards(cards) = &action {
+                    trick.push(cards[0]);
+                }
+                apply_action(&mut round, action, player).unwrap();
+            }
+            played.push([trick[0], trick[1], trick[2]]);
+        }
+        // the later tricks are led by whoever won the one before
+        assert_eq!(round.tricks[1].lead, Seat::Rearhand);
+        let tricks: Vec<[Card; 3]> = round.tricks.iter().map(Trick::play_order).collect();
+        assert_eq!(tricks, played);
+
+        // the given tricks are where the round starts rather than events
+        let built = setup().tricks(&tricks).build().unwrap();
+        assert_eq!(built.state, round.state);
+        assert_eq!(built.tricks, round.tricks);
+        for player in Seat::all().iter() {
+            assert_eq!(built.hand(*player), round.hand(*player));
+        }
+        assert!(built.events().is_empty());
+        assert_eq!(built.deal().tricks, round.tricks);
+
+        // a card can't be played twice
+        played[1][0] = played[0][0];
+        assert_eq!(
+            setup().tricks(&played).build(),
+            Err(RoundError::IllegalPlay(played[0][0]))
+        );
+    }
 }
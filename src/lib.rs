@@ -1,6 +1,11 @@
-// TODO: delete the two lines below before deployment
-#![allow(dead_code)]
-
+pub mod bock;
 pub mod card;
+pub mod cardset;
+pub mod contract;
 pub mod deck;
+pub mod error;
 pub mod game;
+pub mod scoring;
+pub mod seat;
+pub mod sort;
+pub mod table;
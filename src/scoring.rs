@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameResult, RamschResult, Round};
+
+// Seeger-Fabian bonuses
+const WIN_BONUS: i32 = 50;
+const LOSS_PENALTY: i32 = 50;
+const DEFENDER_BONUS: i32 = 40;
+const FOUR_PLAYER_DEFENDER_BONUS: i32 = 30;
+const DURCHMARSCH_VALUE: i32 = 120;
+
+/// What a finished round is worth to each player.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Score {
+    // the game value as written on the score sheet, lost games count double,
+    // in a ramsch what each loser is charged
+    pub value: i32,
+    // Seeger-Fabian list points indexed by seat
+    pub points: [i32; 3],
+    // for the dealer sitting out at a table of four, who is a defender too
+    pub dealer: i32,
+}
+
+/// Score a finished round under the Seeger-Fabian system. `players` is the
+/// number of people at the table, 3 or 4, which decides the defender bonus.
+pub fn score(round: &Round, players: usize) -> Option<Score> {
+    match round.result() {
+        Some(result) => Some(seeger_fabian(&result, players)),
+        None => round.ramsch_result().map(|result| ramsch(&result)),
+    }
+}
+
+/// In a ramsch the losers are charged their card points, doubled for each
+/// jungfrau, unless someone took every trick.
+pub fn ramsch(result: &RamschResult) -> Score {
+    let mut points = [0; 3];
+    if let Some(player) = result.durchmarsch {
+        points[player.index()] = DURCHMARSCH_VALUE;
+        return Score {
+            value: DURCHMARSCH_VALUE,
+            points,
+            dealer: 0,
+        };
+    }
+
+    // tied losers all took the same points so are charged the same
+    let value = match result.losers.first() {
+        Some(loser) => -(result.points[loser.index()] as i32 * (1 << result.jungfrau)),
+        None => 0,
+    };
+    for loser in result.losers.iter() {
+        points[loser.index()] = value;
+    }
+    Score {
+        value,
+        points,
+        dealer: 0,
+    }
+}
+
+pub fn seeger_fabian(result: &GameResult, players: usize) -> Score {
+    // kontra and re double the game value but not the bonuses
+    let value = result.value as i32 * result.modifier as i32;
+    let declarer = result.declarer.index();
+    let mut points = [0; 3];
+
+    if result.won {
+        points[declarer] = value + WIN_BONUS;
+        return Score {
+            value,
+            points,
+            dealer: 0,
+        };
+    }
+
+    let (defender_bonus, dealer) = if players > 3 {
+        (FOUR_PLAYER_DEFENDER_BONUS, FOUR_PLAYER_DEFENDER_BONUS)
+    } else {
+        (DEFENDER_BONUS, 0)
+    };
+    for (player, points) in points.iter_mut().enumerate() {
+        *points = if player == declarer {
+            -2 * value - LOSS_PENALTY
+        } else {
+            defender_bonus
+        };
+    }
+    Score {
+        value: -2 * value,
+        points,
+        dealer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Suit;
+    use crate::contract::{Contract, GameType};
+    use crate::seat::Seat;
+
+    fn result(won: bool, value: u16) -> GameResult {
+        GameResult {
+            declarer: Seat::Middlehand,
+            contract: Contract::new(GameType::Suit(Suit::Heart)),
+            bid: 18,
+            won,
+            points: 61,
+            value,
+            overbid: false,
+            schneider: false,
+            schwarz: false,
+            modifier: 1,
+        }
+    }
+
+    #[test]
+    fn test_seeger_fabian() {
+        assert_eq!(
+            seeger_fabian(&result(true, 30), 3),
+            Score {
+                value: 30,
+                points: [0, 80, 0],
+                dealer: 0,
+            }
+        );
+        assert_eq!(
+            seeger_fabian(&result(false, 30), 3),
+            Score {
+                value: -60,
+                points: [40, -110, 40],
+                dealer: 0,
+            }
+        );
+        assert_eq!(
+            seeger_fabian(&result(false, 30), 4),
+            Score {
+                value: -60,
+                points: [30, -110, 30],
+                dealer: 30,
+            }
+        );
+
+        let kontra = GameResult {
+            modifier: 2,
+            ..result(true, 30)
+        };
+        assert_eq!(
+            seeger_fabian(&kontra, 3),
+            Score {
+                value: 60,
+                points: [0, 110, 0],
+                dealer: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ramsch() {
+        let result = RamschResult {
+            points: [50, 70, 0],
+            losers: vec![Seat::Middlehand],
+            jungfrau: 1,
+            durchmarsch: None,
+        };
+        assert_eq!(
+            ramsch(&result),
+            Score {
+                value: -140,
+                points: [0, -140, 0],
+                dealer: 0,
+            }
+        );
+
+        let result = RamschResult {
+            points: [40, 40, 40],
+            losers: vec![Seat::Forehand, Seat::Middlehand, Seat::Rearhand],
+            jungfrau: 0,
+            durchmarsch: None,
+        };
+        assert_eq!(
+            ramsch(&result),
+            Score {
+                value: -40,
+                points: [-40, -40, -40],
+                dealer: 0,
+            }
+        );
+
+        let result = RamschResult {
+            points: [0, 120, 0],
+            losers: vec![Seat::Middlehand],
+            jungfrau: 2,
+            durchmarsch: Some(Seat::Middlehand),
+        };
+        assert_eq!(
+            ramsch(&result),
+            Score {
+                value: 120,
+                points: [0, 120, 0],
+                dealer: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a player sits for one deal, counted round from the dealer's left.
+/// Forehand is dealt to first and leads the first trick. A seat isn't the
+/// same as a player's chair at the table, which stays put while the deal
+/// moves round.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Seat {
+    Forehand,
+    Middlehand,
+    Rearhand,
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Seat::Forehand => write!(f, "Forehand"),
+            Seat::Middlehand => write!(f, "Middlehand"),
+            Seat::Rearhand => write!(f, "Rearhand"),
+        }
+    }
+}
+
+impl Seat {
+    pub fn all() -> [Seat; 3] {
+        [Seat::Forehand, Seat::Middlehand, Seat::Rearhand]
+    }
+
+    /// The index of the seat in anything kept per seat, like bids or the
+    /// cards of a trick.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Seat> {
+        Seat::all().get(index).copied()
+    }
+
+    /// The seat that plays after this one.
+    pub fn next(&self) -> Seat {
+        Seat::all()[(self.index() + 1) % 3]
+    }
+
+    /// The seat that played before this one.
+    pub fn prev(&self) -> Seat {
+        Seat::all()[(self.index() + 2) % 3]
+    }
+
+    /// The chair in this seat when the player in chair `dealer` deals at a
+    /// table of `chairs`.
+    pub fn chair(&self, dealer: usize, chairs: usize) -> usize {
+        (dealer + 1 + self.index()) % chairs
+    }
+
+    /// The seat of the player in `chair`, None for a dealer who sits the deal
+    /// out at a table of four.
+    pub fn at_chair(chair: usize, dealer: usize, chairs: usize) -> Option<Seat> {
+        Seat::from_index((chair + chairs - dealer - 1) % chairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_and_prev() {
+        for seat in Seat::all().iter() {
+            assert_eq!(seat.next().prev(), *seat);
+            assert_eq!(Seat::from_index(seat.index()), Some(*seat));
+        }
+        assert_eq!(Seat::Rearhand.next(), Seat::Forehand);
+        assert_eq!(Seat::Forehand.prev(), Seat::Rearhand);
+        assert_eq!(Seat::from_index(3), None);
+    }
+
+    #[test]
+    fn test_chairs() {
+        // the dealer is rearhand at a table of three
+        assert_eq!(Seat::Rearhand.chair(1, 3), 1);
+        assert_eq!(Seat::Forehand.chair(1, 3), 2);
+        assert_eq!(Seat::Middlehand.chair(1, 3), 0);
+        assert_eq!(Seat::at_chair(1, 1, 3), Some(Seat::Rearhand));
+
+        // and sits out at a table of four
+        assert_eq!(Seat::at_chair(3, 3, 4), None);
+        for seat in Seat::all().iter() {
+            assert_eq!(Seat::at_chair(seat.chair(3, 4), 3, 4), Some(*seat));
+        }
+    }
+}
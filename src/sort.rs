@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, Suit};
+use crate::contract::{null_order, GameType};
+
+/// How the suits that aren't trumps are laid out in a sorted hand.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum SuitOrder {
+    // clubs, spades, hearts, diamonds
+    Standard,
+    // black and red suits take turns so neighbouring suits are easy to tell
+    // apart
+    Alternating,
+}
+
+// by hand as #[default] on a variant needs a newer Rust than the frontend
+#[allow(clippy::derivable_impls)]
+impl Default for SuitOrder {
+    fn default() -> SuitOrder {
+        SuitOrder::Standard
+    }
+}
+
+/// Sort a hand the way players hold it, highest cards first: the jacks, then
+/// the rest of the trump suit and then the other suits. In null there are no
+/// trumps and the ten sits between the nine and the jack. Without a game,
+/// e.g. while bidding, the hand is sorted as for a grand.
+pub fn sort_hand(cards: &mut [Card], game: Option<GameType>, order: SuitOrder) {
+    let game = game.unwrap_or(GameType::Grand);
+    let trumps = game.trumps();
+    let suits = suit_order(game, order);
+
+    cards.sort_by_key(|card| match trumps.iter().position(|trump| trump == card) {
+        Some(i) => (0, i as u8),
+        None => {
+            let suit = suits.iter().position(|suit| *suit == card.suit).unwrap();
+            let rank = if game == GameType::Null {
+                null_order(card.rank)
+            } else {
+                card.rank as u8
+            };
+            // highest first
+            (1 + suit, 7 - rank)
+        }
+    });
+}
+
+// The trump suit comes first, then the others either in the usual order or
+// alternating in colour.
+fn suit_order(game: GameType, order: SuitOrder) -> Vec<Suit> {
+    let mut suits = vec![Suit::Club, Suit::Spade, Suit::Heart, Suit::Diamond];
+    if let GameType::Suit(trump) = game {
+        suits.retain(|suit| *suit != trump);
+        suits.insert(0, trump);
+    }
+    if order == SuitOrder::Standard {
+        return suits;
+    }
+
+    let mut alternating = vec![suits.remove(0)];
+    while !suits.is_empty() {
+        let last = is_red(alternating[alternating.len() - 1]);
+        let next = suits
+            .iter()
+            .position(|suit| is_red(*suit) != last)
+            .unwrap_or(0);
+        alternating.push(suits.remove(next));
+    }
+    alternating
+}
+
+fn is_red(suit: Suit) -> bool {
+    suit == Suit::Heart || suit == Suit::Diamond
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{format_hand, parse_hand};
+
+    fn sorted(hand: &str, game: Option<GameType>, order: SuitOrder) -> String {
+        let mut cards = parse_hand(hand).unwrap();
+        sort_hand(&mut cards, game, order);
+        format_hand(&cards)
+    }
+
+    #[test]
+    fn test_sort_hand() {
+        let hand = "D7 HJ CA H9 SJ DT S8 HA CK DJ";
+        assert_eq!(
+            sorted(hand, Some(GameType::Suit(Suit::Heart)), SuitOrder::Standard),
+            "SJ HJ DJ HA H9 CA CK S8 DT D7"
+        );
+        assert_eq!(
+            sorted(hand, Some(GameType::Grand), SuitOrder::Standard),
+            "SJ HJ DJ CA CK S8 HA H9 DT D7"
+        );
+        assert_eq!(
+            sorted(hand, None, SuitOrder::Standard),
+            sorted(hand, Some(GameType::Grand), SuitOrder::Standard)
+        );
+        assert_eq!(
+            sorted(hand, Some(GameType::Null), SuitOrder::Standard),
+            "CA CK SJ S8 HA HJ H9 DJ DT D7"
+        );
+    }
+
+    #[test]
+    fn test_alternating() {
+        let hand = "D7 HJ CA H9 SJ DT S8 HA CK DJ";
+        assert_eq!(
+            sorted(
+                hand,
+                Some(GameType::Suit(Suit::Heart)),
+                SuitOrder::Alternating
+            ),
+            "SJ HJ DJ HA H9 CA CK DT D7 S8"
+        );
+        assert_eq!(
+            sorted(hand, Some(GameType::Null), SuitOrder::Alternating),
+            "CA CK HA HJ H9 SJ S8 DJ DT D7"
+        );
+
+        let suits = suit_order(GameType::Suit(Suit::Spade), SuitOrder::Alternating);
+        assert_eq!(
+            suits,
+            vec![Suit::Spade, Suit::Heart, Suit::Club, Suit::Diamond]
+        );
+    }
+}
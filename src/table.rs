@@ -0,0 +1,306 @@
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::bock::{BockRules, Series};
+use crate::error::TableError;
+use crate::game::{
+    apply_action, new_round_with_rng, Action, Mode, Outcome, Round, RoundView, Rules,
+};
+use crate::seat::Seat;
+
+// three play each deal, a fourth player deals and sits out
+const MIN_PLAYERS: usize = 3;
+const MAX_PLAYERS: usize = 4;
+
+/// A game of several deals at one table. Players keep their chairs while
+/// the deal moves one chair round after every round, and the table keeps
+/// everyone's running score. At a table of four the dealer sits each deal
+/// out.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Table {
+    // player ids by chair
+    players: Vec<u32>,
+    // the chair dealing the current round
+    dealer: usize,
+    rounds: Vec<Round>,
+    // running Seeger-Fabian totals by chair
+    scores: Vec<i32>,
+    pub rules: Rules,
+    series: Series,
+}
+
+impl Table {
+    pub fn new(rules: Rules, bock: BockRules) -> Table {
+        Table {
+            players: Vec::new(),
+            dealer: 0,
+            rounds: Vec::new(),
+            scores: Vec::new(),
+            rules,
+            series: Series::new(bock),
+        }
+    }
+
+    /// Sit a player in the next free chair and return the chair. Players can
+    /// only join between rounds.
+    pub fn join(&mut self, player: u32) -> Result<usize, TableError> {
+        if self.players.len() >= MAX_PLAYERS {
+            return Err(TableError::TableFull);
+        }
+        if self.round().map(Round::mode).unwrap_or(Mode::Finished) != Mode::Finished {
+            return Err(TableError::RoundInProgress);
+        }
+        self.players.push(player);
+        self.scores.push(0);
+        Ok(self.players.len() - 1)
+    }
+
+    pub fn players(&self) -> &[u32] {
+        &self.players
+    }
+
+    /// The chair dealing the current round.
+    pub fn dealer(&self) -> usize {
+        self.dealer
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// The round being played or the last one if it's finished.
+    pub fn round(&self) -> Option<&Round> {
+        self.rounds.last()
+    }
+
+    /// The running totals indexed by chair.
+    pub fn scores(&self) -> &[i32] {
+        &self.scores
+    }
+
+    pub fn series(&self) -> &Series {
+        &self.series
+    }
+
+    /// Where a player sits in the current deal.
+    pub fn seat_of(&self, player: u32) -> Option<Seat> {
+        let chair = self.players.iter().position(|id| *id == player)?;
+        Seat::at_chair(chair, self.dealer, self.players.len())
+    }
+
+    /// Whether the player deals the current round and sits it out.
+    pub fn sits_out(&self, player: u32) -> bool {
+        self.players.len() > MIN_PLAYERS && self.players.get(self.dealer) == Some(&player)
+    }
+
+    pub fn view_for(&self, player: u32) -> Option<RoundView> {
+        Some(self.round()?.view_for(self.seat_of(player)?))
+    }
+
+    /// What the dealer sitting out sees of one of the players' hands. They
+    /// can look but don't get any actions.
+    pub fn dealer_view(&self, player: u32, seat: Seat) -> Option<RoundView> {
+        if !self.sits_out(player) {
+            return None;
+        }
+        let mut view = self.round()?.view_for(seat);
+        view.actions.clear();
+        Some(view)
+    }
+
+    /// Apply a player's action to the current round and score the round once
+    /// it's finished.
+    pub fn apply_action(&mut self, player: u32, action: Action) -> Result<Outcome, TableError> {
+        let seat = self.seat_of(player).ok_or(TableError::NotPlaying(player))?;
+        let round = self.rounds.last_mut().ok_or(TableError::NoRound)?;
+        let outcome = apply_action(round, action, seat).map_err(TableError::Action)?;
+        if outcome == Outcome::RoundFinished {
+            self.record();
+        }
+        Ok(outcome)
+    }
+
+    /// Deal the next round once the table is full and the current round is
+    /// finished. The deal moves on a chair each time.
+    pub fn next_round(&mut self) -> Result<&Round, TableError> {
+        self.next_round_with_rng(&mut thread_rng())
+    }
+
+    pub fn next_round_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<&Round, TableError> {
+        if self.players.len() < MIN_PLAYERS {
+            return Err(TableError::NotEnoughPlayers);
+        }
+        if let Some(round) = self.rounds.last() {
+            if round.mode() != Mode::Finished {
+                return Err(TableError::RoundInProgress);
+            }
+            self.dealer = (self.dealer + 1) % self.players.len();
+        }
+
+        let mut round = new_round_with_rng(rng);
+        round.set_rules(self.rules);
+        if self.series.is_ramsch() {
+            round.play_ramsch();
+        }
+        self.rounds.push(round);
+        Ok(&self.rounds[self.rounds.len() - 1])
+    }
+
+    // Add a finished round to the series and the running totals.
+    fn record(&mut self) {
+        let round = match self.rounds.last() {
+            Some(round) => round,
+            None => return,
+        };
+        if let Some(score) = self.series.record(round, self.players.len()) {
+            for seat in Seat::all().iter() {
+                let chair = seat.chair(self.dealer, self.players.len());
+                self.scores[chair] += score.points[seat.index()];
+            }
+            if self.players.len() > MIN_PLAYERS {
+                self.scores[self.dealer] += score.dealer;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ActionError;
+    use crate::game::next_action;
+    use crate::scoring::score;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn full_table(rules: Rules) -> Table {
+        let mut table = Table::new(rules, BockRules::default());
+        for player in [10, 20, 30].iter() {
+            assert!(table.join(*player).is_ok());
+        }
+        table
+    }
+
+    // Play the round out through the table so it gets scored.
+    fn play_out(table: &mut Table) {
+        while let Some((seat, action)) = table.round().and_then(next_action) {
+            let chair = seat.chair(table.dealer(), table.players().len());
+            let player = table.players()[chair];
+            table.apply_action(player, action).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_join() {
+        let mut table = full_table(Rules::default());
+        assert!(table.next_round().is_ok());
+        assert_eq!(table.join(40), Err(TableError::RoundInProgress));
+        assert_eq!(table.players(), &[10, 20, 30]);
+
+        let mut table = full_table(Rules::default());
+        assert_eq!(table.view_for(10), None);
+        assert_eq!(
+            table.apply_action(10, Action::Pass),
+            Err(TableError::NoRound)
+        );
+
+        let mut table = Table::new(Rules::default(), BockRules::default());
+        assert_eq!(table.join(10), Ok(0));
+        assert_eq!(
+            table.next_round().map(|_| ()),
+            Err(TableError::NotEnoughPlayers)
+        );
+    }
+
+    #[test]
+    fn test_dealer_rotation() {
+        let mut table = full_table(Rules::default());
+        let mut rng = StdRng::seed_from_u64(3);
+        assert!(table.next_round_with_rng(&mut rng).is_ok());
+        assert_eq!(table.dealer(), 0);
+        assert_eq!(table.seat_of(20), Some(Seat::Forehand));
+        assert_eq!(table.seat_of(10), Some(Seat::Rearhand));
+        assert_eq!(table.seat_of(99), None);
+        assert_eq!(
+            table.next_round_with_rng(&mut rng).map(|_| ()),
+            Err(TableError::RoundInProgress)
+        );
+        assert_eq!(
+            table.apply_action(10, Action::Pass),
+            Err(TableError::Action(ActionError::NotYourTurn))
+        );
+
+        play_out(&mut table);
+        assert!(table.next_round_with_rng(&mut rng).is_ok());
+        assert_eq!(table.dealer(), 1);
+        assert_eq!(table.seat_of(30), Some(Seat::Forehand));
+        assert_eq!(table.rounds().len(), 2);
+    }
+
+    #[test]
+    fn test_four_players() {
+        let mut table = full_table(Rules::default());
+        assert_eq!(table.join(40), Ok(3));
+        assert_eq!(table.join(50), Err(TableError::TableFull));
+
+        let mut rng = StdRng::seed_from_u64(5);
+        assert!(table.next_round_with_rng(&mut rng).is_ok());
+        assert!(table.sits_out(10));
+        assert_eq!(table.seat_of(10), None);
+        assert_eq!(table.seat_of(20), Some(Seat::Forehand));
+        assert_eq!(table.seat_of(40), Some(Seat::Rearhand));
+        assert_eq!(table.view_for(10), None);
+        assert_eq!(
+            table.apply_action(10, Action::Pass),
+            Err(TableError::NotPlaying(10))
+        );
+
+        // the dealer can look at the hands but not act
+        let view = table.dealer_view(10, Seat::Forehand).unwrap();
+        assert_eq!(view.hand, table.view_for(20).unwrap().hand);
+        assert!(view.actions.is_empty());
+        assert_eq!(table.dealer_view(20, Seat::Middlehand), None);
+
+        play_out(&mut table);
+        let score = score(table.round().unwrap(), 4).unwrap();
+        let mut expected = [0; 4];
+        for seat in Seat::all().iter() {
+            expected[seat.chair(0, 4)] = score.points[seat.index()];
+        }
+        expected[0] = score.dealer;
+        assert_eq!(table.scores(), &expected[..]);
+        // the declarer lost so the dealer gets the defender bonus too
+        assert_eq!(table.scores()[0], 30);
+
+        assert!(table.next_round_with_rng(&mut rng).is_ok());
+        assert_eq!(table.dealer(), 1);
+        assert!(table.sits_out(20));
+        assert_eq!(table.seat_of(30), Some(Seat::Forehand));
+        assert_eq!(table.seat_of(10), Some(Seat::Rearhand));
+        assert_eq!(table.join(50), Err(TableError::TableFull));
+    }
+
+    #[test]
+    fn test_scores() {
+        let rules = Rules {
+            kontra: true,
+            ..Rules::default()
+        };
+        let mut table = full_table(rules);
+        assert!(table.next_round().is_ok());
+        assert!(table.round().unwrap().rules().kontra);
+        play_out(&mut table);
+
+        // somebody always bids so the round is played out and scored
+        let round = table.round().unwrap();
+        let score = score(round, 3).unwrap();
+        let mut expected = [0; 3];
+        for seat in Seat::all().iter() {
+            expected[seat.chair(0, 3)] = score.points[seat.index()];
+        }
+        assert_eq!(table.scores(), &expected[..]);
+    }
+}